@@ -0,0 +1,68 @@
+//! Collision resolution for growing crowds, checking every pair and going through the spatial
+//! hash. `Resolver` switches from one to the other where they cross over. Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use multi_server::broadphase::SpatialHash;
+use multi_server::collision::{Body, Resolver};
+
+const RUNS: usize = 15;
+const PLAYER_SIZE: f32 = 16.0;
+// Enough room per body that a crowd looks like a busy level rather than one solid block.
+const AREA_PER_BODY: f32 = 40.0 * 40.0;
+
+/// `count` bodies scattered at the same density however many there are.
+fn crowd(count: usize) -> Vec<Body> {
+    let side = (count as f32 * AREA_PER_BODY).sqrt();
+    let mut state = 0x9e3779b97f4a7c15u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % 1_000_000) as f32 / 1_000_000.0
+    };
+    (0..count)
+        .map(|_| Body {
+            x: next() * side,
+            y: next() * side,
+            vel_x: next() * 200.0 - 100.0,
+            vel_y: next() * 200.0 - 100.0,
+            size: PLAYER_SIZE,
+            grounded: false,
+        })
+        .collect()
+}
+
+/// Median time of `RUNS` runs, each on a fresh copy of `bodies` but with the same resolver, as
+/// the server keeps one from tick to tick.
+fn median(bodies: &[Body], mut resolver: Resolver) -> Duration {
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let mut bodies = bodies.to_vec();
+            let start = Instant::now();
+            resolver.resolve(black_box(&mut bodies), |_| {});
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    times[RUNS / 2]
+}
+
+fn main() {
+    println!("{:>8} {:>14} {:>14} {:>14} {:>10}", "bodies", "pairs", "grid", "all pairs", "speedup");
+    for count in [10, 50, 100, 200, 1_000, 2_000, 5_000, 10_000] {
+        let bodies = crowd(count);
+        let pairs = SpatialHash::new(2.0 * PLAYER_SIZE).pairs(&bodies).len();
+        let grid = median(&bodies, Resolver::with_threshold(0));
+        let all_pairs = median(&bodies, Resolver::with_threshold(usize::MAX));
+        println!(
+            "{:>8} {:>14} {:>14?} {:>14?} {:>9.1}x",
+            count,
+            pairs,
+            grid,
+            all_pairs,
+            all_pairs.as_secs_f64() / grid.as_secs_f64()
+        );
+    }
+}
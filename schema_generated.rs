@@ -207,328 +207,4179 @@ impl<'a> flatbuffers::Verifiable for PlayerCommand {
 }
 
 impl flatbuffers::SimpleToVerifyInSlice for PlayerCommand {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_CONNECT_STATUS: u8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_CONNECT_STATUS: u8 = 2;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_CONNECT_STATUS: [ConnectStatus; 3] = [
+  ConnectStatus::Accepted,
+  ConnectStatus::Rejected,
+  ConnectStatus::Queued,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct ConnectStatus(pub u8);
+#[allow(non_upper_case_globals)]
+impl ConnectStatus {
+  pub const Accepted: Self = Self(0);
+  pub const Rejected: Self = Self(1);
+  pub const Queued: Self = Self(2);
+
+  pub const ENUM_MIN: u8 = 0;
+  pub const ENUM_MAX: u8 = 2;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Accepted,
+    Self::Rejected,
+    Self::Queued,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Accepted => Some("Accepted"),
+      Self::Rejected => Some("Rejected"),
+      Self::Queued => Some("Queued"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for ConnectStatus {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for ConnectStatus {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for ConnectStatus {
+    type Output = ConnectStatus;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<u8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for ConnectStatus {
+  type Scalar = u8;
+  #[inline]
+  fn to_little_endian(self) -> u8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: u8) -> Self {
+    let b = u8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for ConnectStatus {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    u8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for ConnectStatus {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_REJECT_REASON: u8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_REJECT_REASON: u8 = 3;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_REJECT_REASON: [RejectReason; 4] = [
+  RejectReason::None,
+  RejectReason::ServerFull,
+  RejectReason::VersionMismatch,
+  RejectReason::UnknownRoom,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct RejectReason(pub u8);
+#[allow(non_upper_case_globals)]
+impl RejectReason {
+  pub const None: Self = Self(0);
+  pub const ServerFull: Self = Self(1);
+  pub const VersionMismatch: Self = Self(2);
+  pub const UnknownRoom: Self = Self(3);
+
+  pub const ENUM_MIN: u8 = 0;
+  pub const ENUM_MAX: u8 = 3;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::None,
+    Self::ServerFull,
+    Self::VersionMismatch,
+    Self::UnknownRoom,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::None => Some("None"),
+      Self::ServerFull => Some("ServerFull"),
+      Self::VersionMismatch => Some("VersionMismatch"),
+      Self::UnknownRoom => Some("UnknownRoom"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for RejectReason {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for RejectReason {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for RejectReason {
+    type Output = RejectReason;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<u8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for RejectReason {
+  type Scalar = u8;
+  #[inline]
+  fn to_little_endian(self) -> u8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: u8) -> Self {
+    let b = u8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for RejectReason {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    u8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for RejectReason {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_ADMIN_ACTION: u8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_ADMIN_ACTION: u8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_ADMIN_ACTION: [AdminAction; 1] = [
+  AdminAction::Kick,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct AdminAction(pub u8);
+#[allow(non_upper_case_globals)]
+impl AdminAction {
+  pub const Kick: Self = Self(0);
+
+  pub const ENUM_MIN: u8 = 0;
+  pub const ENUM_MAX: u8 = 0;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Kick,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Kick => Some("Kick"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for AdminAction {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for AdminAction {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for AdminAction {
+    type Output = AdminAction;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<u8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for AdminAction {
+  type Scalar = u8;
+  #[inline]
+  fn to_little_endian(self) -> u8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: u8) -> Self {
+    let b = u8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for AdminAction {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    u8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for AdminAction {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_CLIENT_MESSAGE: u8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_CLIENT_MESSAGE: u8 = 11;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_CLIENT_MESSAGE: [ClientMessage; 12] = [
+  ClientMessage::NONE,
+  ClientMessage::PlayerCommands,
+  ClientMessage::Connect,
+  ClientMessage::Disconnect,
+  ClientMessage::Fragment,
+  ClientMessage::Reliable,
+  ClientMessage::ReliableAck,
+  ClientMessage::Ping,
+  ClientMessage::Chat,
+  ClientMessage::Admin,
+  ClientMessage::PlayerInput,
+  ClientMessage::PathResponse,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct ClientMessage(pub u8);
+#[allow(non_upper_case_globals)]
+impl ClientMessage {
+  pub const NONE: Self = Self(0);
+  pub const PlayerCommands: Self = Self(1);
+  pub const Connect: Self = Self(2);
+  pub const Disconnect: Self = Self(3);
+  pub const Fragment: Self = Self(4);
+  pub const Reliable: Self = Self(5);
+  pub const ReliableAck: Self = Self(6);
+  pub const Ping: Self = Self(7);
+  pub const Chat: Self = Self(8);
+  pub const Admin: Self = Self(9);
+  pub const PlayerInput: Self = Self(10);
+  pub const PathResponse: Self = Self(11);
+
+  pub const ENUM_MIN: u8 = 0;
+  pub const ENUM_MAX: u8 = 11;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::NONE,
+    Self::PlayerCommands,
+    Self::Connect,
+    Self::Disconnect,
+    Self::Fragment,
+    Self::Reliable,
+    Self::ReliableAck,
+    Self::Ping,
+    Self::Chat,
+    Self::Admin,
+    Self::PlayerInput,
+    Self::PathResponse,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::NONE => Some("NONE"),
+      Self::PlayerCommands => Some("PlayerCommands"),
+      Self::Connect => Some("Connect"),
+      Self::Disconnect => Some("Disconnect"),
+      Self::Fragment => Some("Fragment"),
+      Self::Reliable => Some("Reliable"),
+      Self::ReliableAck => Some("ReliableAck"),
+      Self::Ping => Some("Ping"),
+      Self::Chat => Some("Chat"),
+      Self::Admin => Some("Admin"),
+      Self::PlayerInput => Some("PlayerInput"),
+      Self::PathResponse => Some("PathResponse"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for ClientMessage {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for ClientMessage {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for ClientMessage {
+    type Output = ClientMessage;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<u8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for ClientMessage {
+  type Scalar = u8;
+  #[inline]
+  fn to_little_endian(self) -> u8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: u8) -> Self {
+    let b = u8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for ClientMessage {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    u8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for ClientMessage {}
+pub struct ClientMessageUnionTableOffset {}
+
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_LEAVE_REASON: u8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_LEAVE_REASON: u8 = 2;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_LEAVE_REASON: [LeaveReason; 3] = [
+  LeaveReason::Disconnected,
+  LeaveReason::TimedOut,
+  LeaveReason::Kicked,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct LeaveReason(pub u8);
+#[allow(non_upper_case_globals)]
+impl LeaveReason {
+  pub const Disconnected: Self = Self(0);
+  pub const TimedOut: Self = Self(1);
+  pub const Kicked: Self = Self(2);
+
+  pub const ENUM_MIN: u8 = 0;
+  pub const ENUM_MAX: u8 = 2;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Disconnected,
+    Self::TimedOut,
+    Self::Kicked,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Disconnected => Some("Disconnected"),
+      Self::TimedOut => Some("TimedOut"),
+      Self::Kicked => Some("Kicked"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for LeaveReason {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for LeaveReason {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for LeaveReason {
+    type Output = LeaveReason;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<u8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for LeaveReason {
+  type Scalar = u8;
+  #[inline]
+  fn to_little_endian(self) -> u8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: u8) -> Self {
+    let b = u8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for LeaveReason {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    u8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for LeaveReason {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_SERVER_MESSAGE: u8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_SERVER_MESSAGE: u8 = 11;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_SERVER_MESSAGE: [ServerMessage; 12] = [
+  ServerMessage::NONE,
+  ServerMessage::PlayersList,
+  ServerMessage::ConnectResponse,
+  ServerMessage::PlayerLeft,
+  ServerMessage::PlayersDelta,
+  ServerMessage::Fragment,
+  ServerMessage::Reliable,
+  ServerMessage::ReliableAck,
+  ServerMessage::Challenge,
+  ServerMessage::Pong,
+  ServerMessage::Chat,
+  ServerMessage::PathChallenge,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct ServerMessage(pub u8);
+#[allow(non_upper_case_globals)]
+impl ServerMessage {
+  pub const NONE: Self = Self(0);
+  pub const PlayersList: Self = Self(1);
+  pub const ConnectResponse: Self = Self(2);
+  pub const PlayerLeft: Self = Self(3);
+  pub const PlayersDelta: Self = Self(4);
+  pub const Fragment: Self = Self(5);
+  pub const Reliable: Self = Self(6);
+  pub const ReliableAck: Self = Self(7);
+  pub const Challenge: Self = Self(8);
+  pub const Pong: Self = Self(9);
+  pub const Chat: Self = Self(10);
+  pub const PathChallenge: Self = Self(11);
+
+  pub const ENUM_MIN: u8 = 0;
+  pub const ENUM_MAX: u8 = 11;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::NONE,
+    Self::PlayersList,
+    Self::ConnectResponse,
+    Self::PlayerLeft,
+    Self::PlayersDelta,
+    Self::Fragment,
+    Self::Reliable,
+    Self::ReliableAck,
+    Self::Challenge,
+    Self::Pong,
+    Self::Chat,
+    Self::PathChallenge,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::NONE => Some("NONE"),
+      Self::PlayersList => Some("PlayersList"),
+      Self::ConnectResponse => Some("ConnectResponse"),
+      Self::PlayerLeft => Some("PlayerLeft"),
+      Self::PlayersDelta => Some("PlayersDelta"),
+      Self::Fragment => Some("Fragment"),
+      Self::Reliable => Some("Reliable"),
+      Self::ReliableAck => Some("ReliableAck"),
+      Self::Challenge => Some("Challenge"),
+      Self::Pong => Some("Pong"),
+      Self::Chat => Some("Chat"),
+      Self::PathChallenge => Some("PathChallenge"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for ServerMessage {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for ServerMessage {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for ServerMessage {
+    type Output = ServerMessage;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<u8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for ServerMessage {
+  type Scalar = u8;
+  #[inline]
+  fn to_little_endian(self) -> u8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: u8) -> Self {
+    let b = u8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for ServerMessage {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    u8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for ServerMessage {}
+pub struct ServerMessageUnionTableOffset {}
+
 pub enum PlayerOffset {}
 #[derive(Copy, Clone, PartialEq)]
 
-pub struct Player<'a> {
+pub struct Player<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Player<'a> {
+  type Inner = Player<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Player<'a> {
+  pub const VT_X: flatbuffers::VOffsetT = 4;
+  pub const VT_Y: flatbuffers::VOffsetT = 6;
+  pub const VT_COLOR: flatbuffers::VOffsetT = 8;
+  pub const VT_ID: flatbuffers::VOffsetT = 10;
+  pub const VT_AIM_X: flatbuffers::VOffsetT = 12;
+  pub const VT_AIM_Y: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Player { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PlayerArgs
+  ) -> flatbuffers::WIPOffset<Player<'bldr>> {
+    let mut builder = PlayerBuilder::new(_fbb);
+    builder.add_aim_y(args.aim_y);
+    builder.add_aim_x(args.aim_x);
+    builder.add_id(args.id);
+    builder.add_y(args.y);
+    builder.add_x(args.x);
+    builder.add_color(args.color);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn x(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Player::VT_X, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn y(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Player::VT_Y, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn color(&self) -> Color {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Color>(Player::VT_COLOR, Some(Color::Red)).unwrap()}
+  }
+  #[inline]
+  pub fn id(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(Player::VT_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn aim_x(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Player::VT_AIM_X, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn aim_y(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(Player::VT_AIM_Y, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Player<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<f32>("x", Self::VT_X, false)?
+     .visit_field::<f32>("y", Self::VT_Y, false)?
+     .visit_field::<Color>("color", Self::VT_COLOR, false)?
+     .visit_field::<u32>("id", Self::VT_ID, false)?
+     .visit_field::<f32>("aim_x", Self::VT_AIM_X, false)?
+     .visit_field::<f32>("aim_y", Self::VT_AIM_Y, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PlayerArgs {
+    pub x: f32,
+    pub y: f32,
+    pub color: Color,
+    pub id: u32,
+    pub aim_x: f32,
+    pub aim_y: f32,
+}
+impl<'a> Default for PlayerArgs {
+  #[inline]
+  fn default() -> Self {
+    PlayerArgs {
+      x: 0.0,
+      y: 0.0,
+      color: Color::Red,
+      id: 0,
+      aim_x: 0.0,
+      aim_y: 0.0,
+    }
+  }
+}
+
+pub struct PlayerBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PlayerBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_x(&mut self, x: f32) {
+    self.fbb_.push_slot::<f32>(Player::VT_X, x, 0.0);
+  }
+  #[inline]
+  pub fn add_y(&mut self, y: f32) {
+    self.fbb_.push_slot::<f32>(Player::VT_Y, y, 0.0);
+  }
+  #[inline]
+  pub fn add_color(&mut self, color: Color) {
+    self.fbb_.push_slot::<Color>(Player::VT_COLOR, color, Color::Red);
+  }
+  #[inline]
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(Player::VT_ID, id, 0);
+  }
+  #[inline]
+  pub fn add_aim_x(&mut self, aim_x: f32) {
+    self.fbb_.push_slot::<f32>(Player::VT_AIM_X, aim_x, 0.0);
+  }
+  #[inline]
+  pub fn add_aim_y(&mut self, aim_y: f32) {
+    self.fbb_.push_slot::<f32>(Player::VT_AIM_Y, aim_y, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PlayerBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PlayerBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Player<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Player<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Player");
+      ds.field("x", &self.x());
+      ds.field("y", &self.y());
+      ds.field("color", &self.color());
+      ds.field("id", &self.id());
+      ds.field("aim_x", &self.aim_x());
+      ds.field("aim_y", &self.aim_y());
+      ds.finish()
+  }
+}
+pub enum PlayerCommandsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct PlayerCommands<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for PlayerCommands<'a> {
+  type Inner = PlayerCommands<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> PlayerCommands<'a> {
+  pub const VT_COMMANDS: flatbuffers::VOffsetT = 4;
+  pub const VT_SEQUENCE: flatbuffers::VOffsetT = 6;
+  pub const VT_ACK_TICK: flatbuffers::VOffsetT = 8;
+  pub const VT_TICK: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    PlayerCommands { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PlayerCommandsArgs<'args>
+  ) -> flatbuffers::WIPOffset<PlayerCommands<'bldr>> {
+    let mut builder = PlayerCommandsBuilder::new(_fbb);
+    builder.add_tick(args.tick);
+    builder.add_ack_tick(args.ack_tick);
+    builder.add_sequence(args.sequence);
+    if let Some(x) = args.commands { builder.add_commands(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn commands(&self) -> Option<flatbuffers::Vector<'a, PlayerCommand>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, PlayerCommand>>>(PlayerCommands::VT_COMMANDS, None)}
+  }
+  #[inline]
+  pub fn sequence(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(PlayerCommands::VT_SEQUENCE, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn ack_tick(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PlayerCommands::VT_ACK_TICK, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn tick(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PlayerCommands::VT_TICK, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for PlayerCommands<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, PlayerCommand>>>("commands", Self::VT_COMMANDS, false)?
+     .visit_field::<u32>("sequence", Self::VT_SEQUENCE, false)?
+     .visit_field::<u64>("ack_tick", Self::VT_ACK_TICK, false)?
+     .visit_field::<u64>("tick", Self::VT_TICK, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PlayerCommandsArgs<'a> {
+    pub commands: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, PlayerCommand>>>,
+    pub sequence: u32,
+    pub ack_tick: u64,
+    pub tick: u64,
+}
+impl<'a> Default for PlayerCommandsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    PlayerCommandsArgs {
+      commands: None,
+      sequence: 0,
+      ack_tick: 0,
+      tick: 0,
+    }
+  }
+}
+
+pub struct PlayerCommandsBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PlayerCommandsBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_commands(&mut self, commands: flatbuffers::WIPOffset<flatbuffers::Vector<'b , PlayerCommand>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PlayerCommands::VT_COMMANDS, commands);
+  }
+  #[inline]
+  pub fn add_sequence(&mut self, sequence: u32) {
+    self.fbb_.push_slot::<u32>(PlayerCommands::VT_SEQUENCE, sequence, 0);
+  }
+  #[inline]
+  pub fn add_ack_tick(&mut self, ack_tick: u64) {
+    self.fbb_.push_slot::<u64>(PlayerCommands::VT_ACK_TICK, ack_tick, 0);
+  }
+  #[inline]
+  pub fn add_tick(&mut self, tick: u64) {
+    self.fbb_.push_slot::<u64>(PlayerCommands::VT_TICK, tick, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PlayerCommandsBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PlayerCommandsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<PlayerCommands<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for PlayerCommands<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("PlayerCommands");
+      ds.field("commands", &self.commands());
+      ds.field("sequence", &self.sequence());
+      ds.field("ack_tick", &self.ack_tick());
+      ds.field("tick", &self.tick());
+      ds.finish()
+  }
+}
+pub enum PlayerInputOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct PlayerInput<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for PlayerInput<'a> {
+  type Inner = PlayerInput<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> PlayerInput<'a> {
+  pub const VT_SEQUENCE: flatbuffers::VOffsetT = 4;
+  pub const VT_ACK_TICK: flatbuffers::VOffsetT = 6;
+  pub const VT_TICK: flatbuffers::VOffsetT = 8;
+  pub const VT_BUTTONS: flatbuffers::VOffsetT = 10;
+  pub const VT_MOVE_X: flatbuffers::VOffsetT = 12;
+  pub const VT_AIM_X: flatbuffers::VOffsetT = 14;
+  pub const VT_AIM_Y: flatbuffers::VOffsetT = 16;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    PlayerInput { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PlayerInputArgs
+  ) -> flatbuffers::WIPOffset<PlayerInput<'bldr>> {
+    let mut builder = PlayerInputBuilder::new(_fbb);
+    builder.add_tick(args.tick);
+    builder.add_ack_tick(args.ack_tick);
+    builder.add_aim_y(args.aim_y);
+    builder.add_aim_x(args.aim_x);
+    builder.add_move_x(args.move_x);
+    builder.add_sequence(args.sequence);
+    builder.add_buttons(args.buttons);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn sequence(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(PlayerInput::VT_SEQUENCE, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn ack_tick(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PlayerInput::VT_ACK_TICK, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn tick(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PlayerInput::VT_TICK, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn buttons(&self) -> u8 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u8>(PlayerInput::VT_BUTTONS, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn move_x(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(PlayerInput::VT_MOVE_X, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn aim_x(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(PlayerInput::VT_AIM_X, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn aim_y(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(PlayerInput::VT_AIM_Y, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for PlayerInput<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u32>("sequence", Self::VT_SEQUENCE, false)?
+     .visit_field::<u64>("ack_tick", Self::VT_ACK_TICK, false)?
+     .visit_field::<u64>("tick", Self::VT_TICK, false)?
+     .visit_field::<u8>("buttons", Self::VT_BUTTONS, false)?
+     .visit_field::<f32>("move_x", Self::VT_MOVE_X, false)?
+     .visit_field::<f32>("aim_x", Self::VT_AIM_X, false)?
+     .visit_field::<f32>("aim_y", Self::VT_AIM_Y, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PlayerInputArgs {
+    pub sequence: u32,
+    pub ack_tick: u64,
+    pub tick: u64,
+    pub buttons: u8,
+    pub move_x: f32,
+    pub aim_x: f32,
+    pub aim_y: f32,
+}
+impl<'a> Default for PlayerInputArgs {
+  #[inline]
+  fn default() -> Self {
+    PlayerInputArgs {
+      sequence: 0,
+      ack_tick: 0,
+      tick: 0,
+      buttons: 0,
+      move_x: 0.0,
+      aim_x: 0.0,
+      aim_y: 0.0,
+    }
+  }
+}
+
+pub struct PlayerInputBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PlayerInputBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_sequence(&mut self, sequence: u32) {
+    self.fbb_.push_slot::<u32>(PlayerInput::VT_SEQUENCE, sequence, 0);
+  }
+  #[inline]
+  pub fn add_ack_tick(&mut self, ack_tick: u64) {
+    self.fbb_.push_slot::<u64>(PlayerInput::VT_ACK_TICK, ack_tick, 0);
+  }
+  #[inline]
+  pub fn add_tick(&mut self, tick: u64) {
+    self.fbb_.push_slot::<u64>(PlayerInput::VT_TICK, tick, 0);
+  }
+  #[inline]
+  pub fn add_buttons(&mut self, buttons: u8) {
+    self.fbb_.push_slot::<u8>(PlayerInput::VT_BUTTONS, buttons, 0);
+  }
+  #[inline]
+  pub fn add_move_x(&mut self, move_x: f32) {
+    self.fbb_.push_slot::<f32>(PlayerInput::VT_MOVE_X, move_x, 0.0);
+  }
+  #[inline]
+  pub fn add_aim_x(&mut self, aim_x: f32) {
+    self.fbb_.push_slot::<f32>(PlayerInput::VT_AIM_X, aim_x, 0.0);
+  }
+  #[inline]
+  pub fn add_aim_y(&mut self, aim_y: f32) {
+    self.fbb_.push_slot::<f32>(PlayerInput::VT_AIM_Y, aim_y, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PlayerInputBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PlayerInputBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<PlayerInput<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for PlayerInput<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("PlayerInput");
+      ds.field("sequence", &self.sequence());
+      ds.field("ack_tick", &self.ack_tick());
+      ds.field("tick", &self.tick());
+      ds.field("buttons", &self.buttons());
+      ds.field("move_x", &self.move_x());
+      ds.field("aim_x", &self.aim_x());
+      ds.field("aim_y", &self.aim_y());
+      ds.finish()
+  }
+}
+pub enum PlayersListOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct PlayersList<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for PlayersList<'a> {
+  type Inner = PlayersList<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> PlayersList<'a> {
+  pub const VT_PLAYERS: flatbuffers::VOffsetT = 4;
+  pub const VT_LAST_PROCESSED_INPUT: flatbuffers::VOffsetT = 6;
+  pub const VT_TICK: flatbuffers::VOffsetT = 8;
+  pub const VT_YOUR_ID: flatbuffers::VOffsetT = 10;
+  pub const VT_SERVER_TIME: flatbuffers::VOffsetT = 12;
+  pub const VT_INPUT_LEAD: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    PlayersList { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PlayersListArgs<'args>
+  ) -> flatbuffers::WIPOffset<PlayersList<'bldr>> {
+    let mut builder = PlayersListBuilder::new(_fbb);
+    builder.add_server_time(args.server_time);
+    builder.add_tick(args.tick);
+    builder.add_your_id(args.your_id);
+    builder.add_last_processed_input(args.last_processed_input);
+    if let Some(x) = args.players { builder.add_players(x); }
+    builder.add_input_lead(args.input_lead);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn players(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Player<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Player>>>>(PlayersList::VT_PLAYERS, None)}
+  }
+  #[inline]
+  pub fn last_processed_input(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(PlayersList::VT_LAST_PROCESSED_INPUT, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn tick(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PlayersList::VT_TICK, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn your_id(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(PlayersList::VT_YOUR_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn server_time(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PlayersList::VT_SERVER_TIME, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn input_lead(&self) -> u8 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u8>(PlayersList::VT_INPUT_LEAD, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for PlayersList<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Player>>>>("players", Self::VT_PLAYERS, false)?
+     .visit_field::<u32>("last_processed_input", Self::VT_LAST_PROCESSED_INPUT, false)?
+     .visit_field::<u64>("tick", Self::VT_TICK, false)?
+     .visit_field::<u32>("your_id", Self::VT_YOUR_ID, false)?
+     .visit_field::<u64>("server_time", Self::VT_SERVER_TIME, false)?
+     .visit_field::<u8>("input_lead", Self::VT_INPUT_LEAD, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PlayersListArgs<'a> {
+    pub players: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Player<'a>>>>>,
+    pub last_processed_input: u32,
+    pub tick: u64,
+    pub your_id: u32,
+    pub server_time: u64,
+    pub input_lead: u8,
+}
+impl<'a> Default for PlayersListArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    PlayersListArgs {
+      players: None,
+      last_processed_input: 0,
+      tick: 0,
+      your_id: 0,
+      server_time: 0,
+      input_lead: 0,
+    }
+  }
+}
+
+pub struct PlayersListBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PlayersListBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_players(&mut self, players: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Player<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PlayersList::VT_PLAYERS, players);
+  }
+  #[inline]
+  pub fn add_last_processed_input(&mut self, last_processed_input: u32) {
+    self.fbb_.push_slot::<u32>(PlayersList::VT_LAST_PROCESSED_INPUT, last_processed_input, 0);
+  }
+  #[inline]
+  pub fn add_tick(&mut self, tick: u64) {
+    self.fbb_.push_slot::<u64>(PlayersList::VT_TICK, tick, 0);
+  }
+  #[inline]
+  pub fn add_your_id(&mut self, your_id: u32) {
+    self.fbb_.push_slot::<u32>(PlayersList::VT_YOUR_ID, your_id, 0);
+  }
+  #[inline]
+  pub fn add_server_time(&mut self, server_time: u64) {
+    self.fbb_.push_slot::<u64>(PlayersList::VT_SERVER_TIME, server_time, 0);
+  }
+  #[inline]
+  pub fn add_input_lead(&mut self, input_lead: u8) {
+    self.fbb_.push_slot::<u8>(PlayersList::VT_INPUT_LEAD, input_lead, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PlayersListBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PlayersListBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<PlayersList<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for PlayersList<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("PlayersList");
+      ds.field("players", &self.players());
+      ds.field("last_processed_input", &self.last_processed_input());
+      ds.field("tick", &self.tick());
+      ds.field("your_id", &self.your_id());
+      ds.field("server_time", &self.server_time());
+      ds.field("input_lead", &self.input_lead());
+      ds.finish()
+  }
+}
+pub enum PlayerDeltaOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct PlayerDelta<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for PlayerDelta<'a> {
+  type Inner = PlayerDelta<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> PlayerDelta<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_CHANGED: flatbuffers::VOffsetT = 6;
+  pub const VT_X: flatbuffers::VOffsetT = 8;
+  pub const VT_Y: flatbuffers::VOffsetT = 10;
+  pub const VT_COLOR: flatbuffers::VOffsetT = 12;
+  pub const VT_AIM_X: flatbuffers::VOffsetT = 14;
+  pub const VT_AIM_Y: flatbuffers::VOffsetT = 16;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    PlayerDelta { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PlayerDeltaArgs
+  ) -> flatbuffers::WIPOffset<PlayerDelta<'bldr>> {
+    let mut builder = PlayerDeltaBuilder::new(_fbb);
+    builder.add_aim_y(args.aim_y);
+    builder.add_aim_x(args.aim_x);
+    builder.add_y(args.y);
+    builder.add_x(args.x);
+    builder.add_id(args.id);
+    builder.add_color(args.color);
+    builder.add_changed(args.changed);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn id(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(PlayerDelta::VT_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn changed(&self) -> u8 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u8>(PlayerDelta::VT_CHANGED, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn x(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(PlayerDelta::VT_X, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn y(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(PlayerDelta::VT_Y, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn color(&self) -> Color {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Color>(PlayerDelta::VT_COLOR, Some(Color::Red)).unwrap()}
+  }
+  #[inline]
+  pub fn aim_x(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(PlayerDelta::VT_AIM_X, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn aim_y(&self) -> f32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f32>(PlayerDelta::VT_AIM_Y, Some(0.0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for PlayerDelta<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u32>("id", Self::VT_ID, false)?
+     .visit_field::<u8>("changed", Self::VT_CHANGED, false)?
+     .visit_field::<f32>("x", Self::VT_X, false)?
+     .visit_field::<f32>("y", Self::VT_Y, false)?
+     .visit_field::<Color>("color", Self::VT_COLOR, false)?
+     .visit_field::<f32>("aim_x", Self::VT_AIM_X, false)?
+     .visit_field::<f32>("aim_y", Self::VT_AIM_Y, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PlayerDeltaArgs {
+    pub id: u32,
+    pub changed: u8,
+    pub x: f32,
+    pub y: f32,
+    pub color: Color,
+    pub aim_x: f32,
+    pub aim_y: f32,
+}
+impl<'a> Default for PlayerDeltaArgs {
+  #[inline]
+  fn default() -> Self {
+    PlayerDeltaArgs {
+      id: 0,
+      changed: 0,
+      x: 0.0,
+      y: 0.0,
+      color: Color::Red,
+      aim_x: 0.0,
+      aim_y: 0.0,
+    }
+  }
+}
+
+pub struct PlayerDeltaBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PlayerDeltaBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(PlayerDelta::VT_ID, id, 0);
+  }
+  #[inline]
+  pub fn add_changed(&mut self, changed: u8) {
+    self.fbb_.push_slot::<u8>(PlayerDelta::VT_CHANGED, changed, 0);
+  }
+  #[inline]
+  pub fn add_x(&mut self, x: f32) {
+    self.fbb_.push_slot::<f32>(PlayerDelta::VT_X, x, 0.0);
+  }
+  #[inline]
+  pub fn add_y(&mut self, y: f32) {
+    self.fbb_.push_slot::<f32>(PlayerDelta::VT_Y, y, 0.0);
+  }
+  #[inline]
+  pub fn add_color(&mut self, color: Color) {
+    self.fbb_.push_slot::<Color>(PlayerDelta::VT_COLOR, color, Color::Red);
+  }
+  #[inline]
+  pub fn add_aim_x(&mut self, aim_x: f32) {
+    self.fbb_.push_slot::<f32>(PlayerDelta::VT_AIM_X, aim_x, 0.0);
+  }
+  #[inline]
+  pub fn add_aim_y(&mut self, aim_y: f32) {
+    self.fbb_.push_slot::<f32>(PlayerDelta::VT_AIM_Y, aim_y, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PlayerDeltaBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PlayerDeltaBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<PlayerDelta<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for PlayerDelta<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("PlayerDelta");
+      ds.field("id", &self.id());
+      ds.field("changed", &self.changed());
+      ds.field("x", &self.x());
+      ds.field("y", &self.y());
+      ds.field("color", &self.color());
+      ds.field("aim_x", &self.aim_x());
+      ds.field("aim_y", &self.aim_y());
+      ds.finish()
+  }
+}
+pub enum PlayersDeltaOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct PlayersDelta<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for PlayersDelta<'a> {
+  type Inner = PlayersDelta<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> PlayersDelta<'a> {
+  pub const VT_BASELINE_TICK: flatbuffers::VOffsetT = 4;
+  pub const VT_TICK: flatbuffers::VOffsetT = 6;
+  pub const VT_LAST_PROCESSED_INPUT: flatbuffers::VOffsetT = 8;
+  pub const VT_YOUR_ID: flatbuffers::VOffsetT = 10;
+  pub const VT_PLAYERS: flatbuffers::VOffsetT = 12;
+  pub const VT_REMOVED: flatbuffers::VOffsetT = 14;
+  pub const VT_SERVER_TIME: flatbuffers::VOffsetT = 16;
+  pub const VT_INPUT_LEAD: flatbuffers::VOffsetT = 18;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    PlayersDelta { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PlayersDeltaArgs<'args>
+  ) -> flatbuffers::WIPOffset<PlayersDelta<'bldr>> {
+    let mut builder = PlayersDeltaBuilder::new(_fbb);
+    builder.add_server_time(args.server_time);
+    builder.add_tick(args.tick);
+    builder.add_baseline_tick(args.baseline_tick);
+    if let Some(x) = args.removed { builder.add_removed(x); }
+    if let Some(x) = args.players { builder.add_players(x); }
+    builder.add_your_id(args.your_id);
+    builder.add_last_processed_input(args.last_processed_input);
+    builder.add_input_lead(args.input_lead);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn baseline_tick(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PlayersDelta::VT_BASELINE_TICK, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn tick(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PlayersDelta::VT_TICK, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn last_processed_input(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(PlayersDelta::VT_LAST_PROCESSED_INPUT, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn your_id(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(PlayersDelta::VT_YOUR_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn players(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PlayerDelta<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PlayerDelta>>>>(PlayersDelta::VT_PLAYERS, None)}
+  }
+  #[inline]
+  pub fn removed(&self) -> Option<flatbuffers::Vector<'a, u32>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u32>>>(PlayersDelta::VT_REMOVED, None)}
+  }
+  #[inline]
+  pub fn server_time(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PlayersDelta::VT_SERVER_TIME, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn input_lead(&self) -> u8 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u8>(PlayersDelta::VT_INPUT_LEAD, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for PlayersDelta<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("baseline_tick", Self::VT_BASELINE_TICK, false)?
+     .visit_field::<u64>("tick", Self::VT_TICK, false)?
+     .visit_field::<u32>("last_processed_input", Self::VT_LAST_PROCESSED_INPUT, false)?
+     .visit_field::<u32>("your_id", Self::VT_YOUR_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<PlayerDelta>>>>("players", Self::VT_PLAYERS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u32>>>("removed", Self::VT_REMOVED, false)?
+     .visit_field::<u64>("server_time", Self::VT_SERVER_TIME, false)?
+     .visit_field::<u8>("input_lead", Self::VT_INPUT_LEAD, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PlayersDeltaArgs<'a> {
+    pub baseline_tick: u64,
+    pub tick: u64,
+    pub last_processed_input: u32,
+    pub your_id: u32,
+    pub players: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PlayerDelta<'a>>>>>,
+    pub removed: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u32>>>,
+    pub server_time: u64,
+    pub input_lead: u8,
+}
+impl<'a> Default for PlayersDeltaArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    PlayersDeltaArgs {
+      baseline_tick: 0,
+      tick: 0,
+      last_processed_input: 0,
+      your_id: 0,
+      players: None,
+      removed: None,
+      server_time: 0,
+      input_lead: 0,
+    }
+  }
+}
+
+pub struct PlayersDeltaBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PlayersDeltaBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_baseline_tick(&mut self, baseline_tick: u64) {
+    self.fbb_.push_slot::<u64>(PlayersDelta::VT_BASELINE_TICK, baseline_tick, 0);
+  }
+  #[inline]
+  pub fn add_tick(&mut self, tick: u64) {
+    self.fbb_.push_slot::<u64>(PlayersDelta::VT_TICK, tick, 0);
+  }
+  #[inline]
+  pub fn add_last_processed_input(&mut self, last_processed_input: u32) {
+    self.fbb_.push_slot::<u32>(PlayersDelta::VT_LAST_PROCESSED_INPUT, last_processed_input, 0);
+  }
+  #[inline]
+  pub fn add_your_id(&mut self, your_id: u32) {
+    self.fbb_.push_slot::<u32>(PlayersDelta::VT_YOUR_ID, your_id, 0);
+  }
+  #[inline]
+  pub fn add_players(&mut self, players: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<PlayerDelta<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PlayersDelta::VT_PLAYERS, players);
+  }
+  #[inline]
+  pub fn add_removed(&mut self, removed: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u32>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PlayersDelta::VT_REMOVED, removed);
+  }
+  #[inline]
+  pub fn add_server_time(&mut self, server_time: u64) {
+    self.fbb_.push_slot::<u64>(PlayersDelta::VT_SERVER_TIME, server_time, 0);
+  }
+  #[inline]
+  pub fn add_input_lead(&mut self, input_lead: u8) {
+    self.fbb_.push_slot::<u8>(PlayersDelta::VT_INPUT_LEAD, input_lead, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PlayersDeltaBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PlayersDeltaBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<PlayersDelta<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for PlayersDelta<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("PlayersDelta");
+      ds.field("baseline_tick", &self.baseline_tick());
+      ds.field("tick", &self.tick());
+      ds.field("last_processed_input", &self.last_processed_input());
+      ds.field("your_id", &self.your_id());
+      ds.field("players", &self.players());
+      ds.field("removed", &self.removed());
+      ds.field("server_time", &self.server_time());
+      ds.field("input_lead", &self.input_lead());
+      ds.finish()
+  }
+}
+pub enum FragmentOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Fragment<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Fragment<'a> {
+  type Inner = Fragment<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Fragment<'a> {
+  pub const VT_MESSAGE_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_INDEX: flatbuffers::VOffsetT = 6;
+  pub const VT_COUNT: flatbuffers::VOffsetT = 8;
+  pub const VT_DATA: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Fragment { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args FragmentArgs<'args>
+  ) -> flatbuffers::WIPOffset<Fragment<'bldr>> {
+    let mut builder = FragmentBuilder::new(_fbb);
+    if let Some(x) = args.data { builder.add_data(x); }
+    builder.add_count(args.count);
+    builder.add_index(args.index);
+    builder.add_message_id(args.message_id);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn message_id(&self) -> u16 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u16>(Fragment::VT_MESSAGE_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn index(&self) -> u16 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u16>(Fragment::VT_INDEX, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn count(&self) -> u16 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u16>(Fragment::VT_COUNT, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn data(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Fragment::VT_DATA, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Fragment<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u16>("message_id", Self::VT_MESSAGE_ID, false)?
+     .visit_field::<u16>("index", Self::VT_INDEX, false)?
+     .visit_field::<u16>("count", Self::VT_COUNT, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("data", Self::VT_DATA, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct FragmentArgs<'a> {
+    pub message_id: u16,
+    pub index: u16,
+    pub count: u16,
+    pub data: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+}
+impl<'a> Default for FragmentArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    FragmentArgs {
+      message_id: 0,
+      index: 0,
+      count: 0,
+      data: None,
+    }
+  }
+}
+
+pub struct FragmentBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> FragmentBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_message_id(&mut self, message_id: u16) {
+    self.fbb_.push_slot::<u16>(Fragment::VT_MESSAGE_ID, message_id, 0);
+  }
+  #[inline]
+  pub fn add_index(&mut self, index: u16) {
+    self.fbb_.push_slot::<u16>(Fragment::VT_INDEX, index, 0);
+  }
+  #[inline]
+  pub fn add_count(&mut self, count: u16) {
+    self.fbb_.push_slot::<u16>(Fragment::VT_COUNT, count, 0);
+  }
+  #[inline]
+  pub fn add_data(&mut self, data: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Fragment::VT_DATA, data);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> FragmentBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    FragmentBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Fragment<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Fragment<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Fragment");
+      ds.field("message_id", &self.message_id());
+      ds.field("index", &self.index());
+      ds.field("count", &self.count());
+      ds.field("data", &self.data());
+      ds.finish()
+  }
+}
+pub enum ReliableOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Reliable<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Reliable<'a> {
+  type Inner = Reliable<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Reliable<'a> {
+  pub const VT_SEQUENCE: flatbuffers::VOffsetT = 4;
+  pub const VT_PAYLOAD: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Reliable { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ReliableArgs<'args>
+  ) -> flatbuffers::WIPOffset<Reliable<'bldr>> {
+    let mut builder = ReliableBuilder::new(_fbb);
+    if let Some(x) = args.payload { builder.add_payload(x); }
+    builder.add_sequence(args.sequence);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn sequence(&self) -> u16 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u16>(Reliable::VT_SEQUENCE, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn payload(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Reliable::VT_PAYLOAD, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Reliable<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u16>("sequence", Self::VT_SEQUENCE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("payload", Self::VT_PAYLOAD, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ReliableArgs<'a> {
+    pub sequence: u16,
+    pub payload: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+}
+impl<'a> Default for ReliableArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ReliableArgs {
+      sequence: 0,
+      payload: None,
+    }
+  }
+}
+
+pub struct ReliableBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ReliableBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_sequence(&mut self, sequence: u16) {
+    self.fbb_.push_slot::<u16>(Reliable::VT_SEQUENCE, sequence, 0);
+  }
+  #[inline]
+  pub fn add_payload(&mut self, payload: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Reliable::VT_PAYLOAD, payload);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ReliableBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ReliableBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Reliable<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Reliable<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Reliable");
+      ds.field("sequence", &self.sequence());
+      ds.field("payload", &self.payload());
+      ds.finish()
+  }
+}
+pub enum ReliableAckOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ReliableAck<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ReliableAck<'a> {
+  type Inner = ReliableAck<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ReliableAck<'a> {
+  pub const VT_ACK: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ReliableAck { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ReliableAckArgs
+  ) -> flatbuffers::WIPOffset<ReliableAck<'bldr>> {
+    let mut builder = ReliableAckBuilder::new(_fbb);
+    builder.add_ack(args.ack);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn ack(&self) -> u16 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u16>(ReliableAck::VT_ACK, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for ReliableAck<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u16>("ack", Self::VT_ACK, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ReliableAckArgs {
+    pub ack: u16,
+}
+impl<'a> Default for ReliableAckArgs {
+  #[inline]
+  fn default() -> Self {
+    ReliableAckArgs {
+      ack: 0,
+    }
+  }
+}
+
+pub struct ReliableAckBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ReliableAckBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_ack(&mut self, ack: u16) {
+    self.fbb_.push_slot::<u16>(ReliableAck::VT_ACK, ack, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ReliableAckBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ReliableAckBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ReliableAck<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ReliableAck<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ReliableAck");
+      ds.field("ack", &self.ack());
+      ds.finish()
+  }
+}
+pub enum ConnectOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Connect<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Connect<'a> {
+  type Inner = Connect<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Connect<'a> {
+  pub const VT_COOKIE: flatbuffers::VOffsetT = 4;
+  pub const VT_PADDING: flatbuffers::VOffsetT = 6;
+  pub const VT_PUBLIC_KEY: flatbuffers::VOffsetT = 8;
+  pub const VT_ROOM: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Connect { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ConnectArgs<'args>
+  ) -> flatbuffers::WIPOffset<Connect<'bldr>> {
+    let mut builder = ConnectBuilder::new(_fbb);
+    if let Some(x) = args.room { builder.add_room(x); }
+    if let Some(x) = args.public_key { builder.add_public_key(x); }
+    if let Some(x) = args.padding { builder.add_padding(x); }
+    if let Some(x) = args.cookie { builder.add_cookie(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn cookie(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Connect::VT_COOKIE, None)}
+  }
+  #[inline]
+  pub fn padding(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Connect::VT_PADDING, None)}
+  }
+  #[inline]
+  pub fn public_key(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Connect::VT_PUBLIC_KEY, None)}
+  }
+  #[inline]
+  pub fn room(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Connect::VT_ROOM, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Connect<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("cookie", Self::VT_COOKIE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("padding", Self::VT_PADDING, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("public_key", Self::VT_PUBLIC_KEY, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("room", Self::VT_ROOM, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ConnectArgs<'a> {
+    pub cookie: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub padding: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub public_key: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub room: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for ConnectArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ConnectArgs {
+      cookie: None,
+      padding: None,
+      public_key: None,
+      room: None,
+    }
+  }
+}
+
+pub struct ConnectBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ConnectBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_cookie(&mut self, cookie: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Connect::VT_COOKIE, cookie);
+  }
+  #[inline]
+  pub fn add_padding(&mut self, padding: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Connect::VT_PADDING, padding);
+  }
+  #[inline]
+  pub fn add_public_key(&mut self, public_key: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Connect::VT_PUBLIC_KEY, public_key);
+  }
+  #[inline]
+  pub fn add_room(&mut self, room: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Connect::VT_ROOM, room);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ConnectBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ConnectBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Connect<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Connect<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Connect");
+      ds.field("cookie", &self.cookie());
+      ds.field("padding", &self.padding());
+      ds.field("public_key", &self.public_key());
+      ds.field("room", &self.room());
+      ds.finish()
+  }
+}
+pub enum ChallengeOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Challenge<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Challenge<'a> {
+  type Inner = Challenge<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Challenge<'a> {
+  pub const VT_COOKIE: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Challenge { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ChallengeArgs<'args>
+  ) -> flatbuffers::WIPOffset<Challenge<'bldr>> {
+    let mut builder = ChallengeBuilder::new(_fbb);
+    if let Some(x) = args.cookie { builder.add_cookie(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn cookie(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Challenge::VT_COOKIE, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Challenge<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("cookie", Self::VT_COOKIE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ChallengeArgs<'a> {
+    pub cookie: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+}
+impl<'a> Default for ChallengeArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ChallengeArgs {
+      cookie: None,
+    }
+  }
+}
+
+pub struct ChallengeBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ChallengeBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_cookie(&mut self, cookie: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Challenge::VT_COOKIE, cookie);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ChallengeBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ChallengeBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Challenge<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Challenge<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Challenge");
+      ds.field("cookie", &self.cookie());
+      ds.finish()
+  }
+}
+pub enum DisconnectOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Disconnect<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Disconnect<'a> {
+  type Inner = Disconnect<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Disconnect<'a> {
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Disconnect { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    _args: &'args DisconnectArgs
+  ) -> flatbuffers::WIPOffset<Disconnect<'bldr>> {
+    let mut builder = DisconnectBuilder::new(_fbb);
+    builder.finish()
+  }
+
+}
+
+impl flatbuffers::Verifiable for Disconnect<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct DisconnectArgs {
+}
+impl<'a> Default for DisconnectArgs {
+  #[inline]
+  fn default() -> Self {
+    DisconnectArgs {
+    }
+  }
+}
+
+pub struct DisconnectBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> DisconnectBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> DisconnectBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    DisconnectBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Disconnect<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Disconnect<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Disconnect");
+      ds.finish()
+  }
+}
+pub enum ClientPacketOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ClientPacket<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ClientPacket<'a> {
+  type Inner = ClientPacket<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ClientPacket<'a> {
+  pub const VT_MESSAGE_TYPE: flatbuffers::VOffsetT = 4;
+  pub const VT_MESSAGE: flatbuffers::VOffsetT = 6;
+  pub const VT_SESSION_ID: flatbuffers::VOffsetT = 8;
+  pub const VT_PROTOCOL_VERSION: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ClientPacket { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ClientPacketArgs
+  ) -> flatbuffers::WIPOffset<ClientPacket<'bldr>> {
+    let mut builder = ClientPacketBuilder::new(_fbb);
+    builder.add_session_id(args.session_id);
+    if let Some(x) = args.message { builder.add_message(x); }
+    builder.add_protocol_version(args.protocol_version);
+    builder.add_message_type(args.message_type);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn message_type(&self) -> ClientMessage {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<ClientMessage>(ClientPacket::VT_MESSAGE_TYPE, Some(ClientMessage::NONE)).unwrap()}
+  }
+  #[inline]
+  pub fn message(&self) -> Option<flatbuffers::Table<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(ClientPacket::VT_MESSAGE, None)}
+  }
+  #[inline]
+  pub fn session_id(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(ClientPacket::VT_SESSION_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn protocol_version(&self) -> u16 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u16>(ClientPacket::VT_PROTOCOL_VERSION, Some(0)).unwrap()}
+  }
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_player_commands(&self) -> Option<PlayerCommands<'a>> {
+    if self.message_type() == ClientMessage::PlayerCommands {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { PlayerCommands::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_connect(&self) -> Option<Connect<'a>> {
+    if self.message_type() == ClientMessage::Connect {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Connect::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_disconnect(&self) -> Option<Disconnect<'a>> {
+    if self.message_type() == ClientMessage::Disconnect {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Disconnect::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_fragment(&self) -> Option<Fragment<'a>> {
+    if self.message_type() == ClientMessage::Fragment {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Fragment::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_reliable(&self) -> Option<Reliable<'a>> {
+    if self.message_type() == ClientMessage::Reliable {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Reliable::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_reliable_ack(&self) -> Option<ReliableAck<'a>> {
+    if self.message_type() == ClientMessage::ReliableAck {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { ReliableAck::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_ping(&self) -> Option<Ping<'a>> {
+    if self.message_type() == ClientMessage::Ping {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Ping::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_chat(&self) -> Option<Chat<'a>> {
+    if self.message_type() == ClientMessage::Chat {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Chat::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_admin(&self) -> Option<Admin<'a>> {
+    if self.message_type() == ClientMessage::Admin {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Admin::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_player_input(&self) -> Option<PlayerInput<'a>> {
+    if self.message_type() == ClientMessage::PlayerInput {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { PlayerInput::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_path_response(&self) -> Option<PathResponse<'a>> {
+    if self.message_type() == ClientMessage::PathResponse {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { PathResponse::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+}
+
+impl flatbuffers::Verifiable for ClientPacket<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_union::<ClientMessage, _>("message_type", Self::VT_MESSAGE_TYPE, "message", Self::VT_MESSAGE, false, |key, v, pos| {
+        match key {
+          ClientMessage::PlayerCommands => v.verify_union_variant::<flatbuffers::ForwardsUOffset<PlayerCommands>>("ClientMessage::PlayerCommands", pos),
+          ClientMessage::Connect => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Connect>>("ClientMessage::Connect", pos),
+          ClientMessage::Disconnect => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Disconnect>>("ClientMessage::Disconnect", pos),
+          ClientMessage::Fragment => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Fragment>>("ClientMessage::Fragment", pos),
+          ClientMessage::Reliable => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Reliable>>("ClientMessage::Reliable", pos),
+          ClientMessage::ReliableAck => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ReliableAck>>("ClientMessage::ReliableAck", pos),
+          ClientMessage::Ping => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Ping>>("ClientMessage::Ping", pos),
+          ClientMessage::Chat => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Chat>>("ClientMessage::Chat", pos),
+          ClientMessage::Admin => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Admin>>("ClientMessage::Admin", pos),
+          ClientMessage::PlayerInput => v.verify_union_variant::<flatbuffers::ForwardsUOffset<PlayerInput>>("ClientMessage::PlayerInput", pos),
+          ClientMessage::PathResponse => v.verify_union_variant::<flatbuffers::ForwardsUOffset<PathResponse>>("ClientMessage::PathResponse", pos),
+          _ => Ok(()),
+        }
+     })?
+     .visit_field::<u64>("session_id", Self::VT_SESSION_ID, false)?
+     .visit_field::<u16>("protocol_version", Self::VT_PROTOCOL_VERSION, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ClientPacketArgs {
+    pub message_type: ClientMessage,
+    pub message: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
+    pub session_id: u64,
+    pub protocol_version: u16,
+}
+impl<'a> Default for ClientPacketArgs {
+  #[inline]
+  fn default() -> Self {
+    ClientPacketArgs {
+      message_type: ClientMessage::NONE,
+      message: None,
+      session_id: 0,
+      protocol_version: 0,
+    }
+  }
+}
+
+pub struct ClientPacketBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ClientPacketBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_message_type(&mut self, message_type: ClientMessage) {
+    self.fbb_.push_slot::<ClientMessage>(ClientPacket::VT_MESSAGE_TYPE, message_type, ClientMessage::NONE);
+  }
+  #[inline]
+  pub fn add_message(&mut self, message: flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ClientPacket::VT_MESSAGE, message);
+  }
+  #[inline]
+  pub fn add_session_id(&mut self, session_id: u64) {
+    self.fbb_.push_slot::<u64>(ClientPacket::VT_SESSION_ID, session_id, 0);
+  }
+  #[inline]
+  pub fn add_protocol_version(&mut self, protocol_version: u16) {
+    self.fbb_.push_slot::<u16>(ClientPacket::VT_PROTOCOL_VERSION, protocol_version, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ClientPacketBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ClientPacketBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ClientPacket<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ClientPacket<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ClientPacket");
+      ds.field("message_type", &self.message_type());
+      match self.message_type() {
+        ClientMessage::PlayerCommands => {
+          if let Some(x) = self.message_as_player_commands() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ClientMessage::Connect => {
+          if let Some(x) = self.message_as_connect() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ClientMessage::Disconnect => {
+          if let Some(x) = self.message_as_disconnect() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ClientMessage::Fragment => {
+          if let Some(x) = self.message_as_fragment() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ClientMessage::Reliable => {
+          if let Some(x) = self.message_as_reliable() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ClientMessage::ReliableAck => {
+          if let Some(x) = self.message_as_reliable_ack() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ClientMessage::Ping => {
+          if let Some(x) = self.message_as_ping() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ClientMessage::Chat => {
+          if let Some(x) = self.message_as_chat() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ClientMessage::Admin => {
+          if let Some(x) = self.message_as_admin() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ClientMessage::PlayerInput => {
+          if let Some(x) = self.message_as_player_input() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ClientMessage::PathResponse => {
+          if let Some(x) = self.message_as_path_response() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        _ => {
+          let x: Option<()> = None;
+          ds.field("message", &x)
+        },
+      };
+      ds.field("session_id", &self.session_id());
+      ds.field("protocol_version", &self.protocol_version());
+      ds.finish()
+  }
+}
+pub enum PingOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Ping<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Ping<'a> {
+  type Inner = Ping<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Ping<'a> {
+  pub const VT_CLIENT_TIME: flatbuffers::VOffsetT = 4;
+  pub const VT_LAST_PONG_SERVER_TIME: flatbuffers::VOffsetT = 6;
+  pub const VT_LAST_PONG_RECEIVED: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Ping { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PingArgs
+  ) -> flatbuffers::WIPOffset<Ping<'bldr>> {
+    let mut builder = PingBuilder::new(_fbb);
+    builder.add_last_pong_received(args.last_pong_received);
+    builder.add_last_pong_server_time(args.last_pong_server_time);
+    builder.add_client_time(args.client_time);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn client_time(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Ping::VT_CLIENT_TIME, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn last_pong_server_time(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Ping::VT_LAST_PONG_SERVER_TIME, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn last_pong_received(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Ping::VT_LAST_PONG_RECEIVED, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Ping<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("client_time", Self::VT_CLIENT_TIME, false)?
+     .visit_field::<u64>("last_pong_server_time", Self::VT_LAST_PONG_SERVER_TIME, false)?
+     .visit_field::<u64>("last_pong_received", Self::VT_LAST_PONG_RECEIVED, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PingArgs {
+    pub client_time: u64,
+    pub last_pong_server_time: u64,
+    pub last_pong_received: u64,
+}
+impl<'a> Default for PingArgs {
+  #[inline]
+  fn default() -> Self {
+    PingArgs {
+      client_time: 0,
+      last_pong_server_time: 0,
+      last_pong_received: 0,
+    }
+  }
+}
+
+pub struct PingBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PingBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_client_time(&mut self, client_time: u64) {
+    self.fbb_.push_slot::<u64>(Ping::VT_CLIENT_TIME, client_time, 0);
+  }
+  #[inline]
+  pub fn add_last_pong_server_time(&mut self, last_pong_server_time: u64) {
+    self.fbb_.push_slot::<u64>(Ping::VT_LAST_PONG_SERVER_TIME, last_pong_server_time, 0);
+  }
+  #[inline]
+  pub fn add_last_pong_received(&mut self, last_pong_received: u64) {
+    self.fbb_.push_slot::<u64>(Ping::VT_LAST_PONG_RECEIVED, last_pong_received, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PingBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PingBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Ping<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Ping<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Ping");
+      ds.field("client_time", &self.client_time());
+      ds.field("last_pong_server_time", &self.last_pong_server_time());
+      ds.field("last_pong_received", &self.last_pong_received());
+      ds.finish()
+  }
+}
+pub enum PongOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Pong<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Pong<'a> {
+  type Inner = Pong<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Pong<'a> {
+  pub const VT_CLIENT_TIME: flatbuffers::VOffsetT = 4;
+  pub const VT_SERVER_TIME: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Pong { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PongArgs
+  ) -> flatbuffers::WIPOffset<Pong<'bldr>> {
+    let mut builder = PongBuilder::new(_fbb);
+    builder.add_server_time(args.server_time);
+    builder.add_client_time(args.client_time);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn client_time(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Pong::VT_CLIENT_TIME, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn server_time(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Pong::VT_SERVER_TIME, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Pong<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("client_time", Self::VT_CLIENT_TIME, false)?
+     .visit_field::<u64>("server_time", Self::VT_SERVER_TIME, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PongArgs {
+    pub client_time: u64,
+    pub server_time: u64,
+}
+impl<'a> Default for PongArgs {
+  #[inline]
+  fn default() -> Self {
+    PongArgs {
+      client_time: 0,
+      server_time: 0,
+    }
+  }
+}
+
+pub struct PongBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PongBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_client_time(&mut self, client_time: u64) {
+    self.fbb_.push_slot::<u64>(Pong::VT_CLIENT_TIME, client_time, 0);
+  }
+  #[inline]
+  pub fn add_server_time(&mut self, server_time: u64) {
+    self.fbb_.push_slot::<u64>(Pong::VT_SERVER_TIME, server_time, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PongBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PongBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Pong<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Pong<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Pong");
+      ds.field("client_time", &self.client_time());
+      ds.field("server_time", &self.server_time());
+      ds.finish()
+  }
+}
+pub enum PathChallengeOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct PathChallenge<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for PathChallenge<'a> {
+  type Inner = PathChallenge<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> PathChallenge<'a> {
+  pub const VT_NONCE: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    PathChallenge { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PathChallengeArgs
+  ) -> flatbuffers::WIPOffset<PathChallenge<'bldr>> {
+    let mut builder = PathChallengeBuilder::new(_fbb);
+    builder.add_nonce(args.nonce);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn nonce(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PathChallenge::VT_NONCE, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for PathChallenge<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PathChallengeArgs {
+    pub nonce: u64,
+}
+impl<'a> Default for PathChallengeArgs {
+  #[inline]
+  fn default() -> Self {
+    PathChallengeArgs {
+      nonce: 0,
+    }
+  }
+}
+
+pub struct PathChallengeBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PathChallengeBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_nonce(&mut self, nonce: u64) {
+    self.fbb_.push_slot::<u64>(PathChallenge::VT_NONCE, nonce, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PathChallengeBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PathChallengeBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<PathChallenge<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for PathChallenge<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("PathChallenge");
+      ds.field("nonce", &self.nonce());
+      ds.finish()
+  }
+}
+pub enum PathResponseOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct PathResponse<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for PathResponse<'a> {
+  type Inner = PathResponse<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> PathResponse<'a> {
+  pub const VT_NONCE: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    PathResponse { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PathResponseArgs
+  ) -> flatbuffers::WIPOffset<PathResponse<'bldr>> {
+    let mut builder = PathResponseBuilder::new(_fbb);
+    builder.add_nonce(args.nonce);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn nonce(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PathResponse::VT_NONCE, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for PathResponse<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("nonce", Self::VT_NONCE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PathResponseArgs {
+    pub nonce: u64,
+}
+impl<'a> Default for PathResponseArgs {
+  #[inline]
+  fn default() -> Self {
+    PathResponseArgs {
+      nonce: 0,
+    }
+  }
+}
+
+pub struct PathResponseBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PathResponseBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_nonce(&mut self, nonce: u64) {
+    self.fbb_.push_slot::<u64>(PathResponse::VT_NONCE, nonce, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PathResponseBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PathResponseBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<PathResponse<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for PathResponse<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("PathResponse");
+      ds.field("nonce", &self.nonce());
+      ds.finish()
+  }
+}
+pub enum ChatOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Chat<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Chat<'a> {
+  type Inner = Chat<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Chat<'a> {
+  pub const VT_PLAYER_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_TEXT: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Chat { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ChatArgs<'args>
+  ) -> flatbuffers::WIPOffset<Chat<'bldr>> {
+    let mut builder = ChatBuilder::new(_fbb);
+    if let Some(x) = args.text { builder.add_text(x); }
+    builder.add_player_id(args.player_id);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn player_id(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(Chat::VT_PLAYER_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn text(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Chat::VT_TEXT, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Chat<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u32>("player_id", Self::VT_PLAYER_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("text", Self::VT_TEXT, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ChatArgs<'a> {
+    pub player_id: u32,
+    pub text: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for ChatArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ChatArgs {
+      player_id: 0,
+      text: None,
+    }
+  }
+}
+
+pub struct ChatBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ChatBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_player_id(&mut self, player_id: u32) {
+    self.fbb_.push_slot::<u32>(Chat::VT_PLAYER_ID, player_id, 0);
+  }
+  #[inline]
+  pub fn add_text(&mut self, text: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Chat::VT_TEXT, text);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ChatBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ChatBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Chat<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Chat<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Chat");
+      ds.field("player_id", &self.player_id());
+      ds.field("text", &self.text());
+      ds.finish()
+  }
+}
+pub enum AdminOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Admin<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Admin<'a> {
+  type Inner = Admin<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Admin<'a> {
+  pub const VT_TOKEN: flatbuffers::VOffsetT = 4;
+  pub const VT_ACTION: flatbuffers::VOffsetT = 6;
+  pub const VT_PLAYER_ID: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Admin { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args AdminArgs<'args>
+  ) -> flatbuffers::WIPOffset<Admin<'bldr>> {
+    let mut builder = AdminBuilder::new(_fbb);
+    builder.add_player_id(args.player_id);
+    if let Some(x) = args.token { builder.add_token(x); }
+    builder.add_action(args.action);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn token(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Admin::VT_TOKEN, None)}
+  }
+  #[inline]
+  pub fn action(&self) -> AdminAction {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<AdminAction>(Admin::VT_ACTION, Some(AdminAction::Kick)).unwrap()}
+  }
+  #[inline]
+  pub fn player_id(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(Admin::VT_PLAYER_ID, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Admin<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("token", Self::VT_TOKEN, false)?
+     .visit_field::<AdminAction>("action", Self::VT_ACTION, false)?
+     .visit_field::<u32>("player_id", Self::VT_PLAYER_ID, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct AdminArgs<'a> {
+    pub token: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub action: AdminAction,
+    pub player_id: u32,
+}
+impl<'a> Default for AdminArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    AdminArgs {
+      token: None,
+      action: AdminAction::Kick,
+      player_id: 0,
+    }
+  }
+}
+
+pub struct AdminBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> AdminBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_token(&mut self, token: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Admin::VT_TOKEN, token);
+  }
+  #[inline]
+  pub fn add_action(&mut self, action: AdminAction) {
+    self.fbb_.push_slot::<AdminAction>(Admin::VT_ACTION, action, AdminAction::Kick);
+  }
+  #[inline]
+  pub fn add_player_id(&mut self, player_id: u32) {
+    self.fbb_.push_slot::<u32>(Admin::VT_PLAYER_ID, player_id, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> AdminBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    AdminBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Admin<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Admin<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Admin");
+      ds.field("token", &self.token());
+      ds.field("action", &self.action());
+      ds.field("player_id", &self.player_id());
+      ds.finish()
+  }
+}
+pub enum ConnectResponseOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ConnectResponse<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for Player<'a> {
-  type Inner = Player<'a>;
+impl<'a> flatbuffers::Follow<'a> for ConnectResponse<'a> {
+  type Inner = ConnectResponse<'a>;
   #[inline]
   unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
     Self { _tab: flatbuffers::Table::new(buf, loc) }
   }
 }
 
-impl<'a> Player<'a> {
-  pub const VT_X: flatbuffers::VOffsetT = 4;
-  pub const VT_Y: flatbuffers::VOffsetT = 6;
-  pub const VT_COLOR: flatbuffers::VOffsetT = 8;
+impl<'a> ConnectResponse<'a> {
+  pub const VT_STATUS: flatbuffers::VOffsetT = 4;
+  pub const VT_SESSION_ID: flatbuffers::VOffsetT = 6;
+  pub const VT_REASON: flatbuffers::VOffsetT = 8;
+  pub const VT_QUEUE_POSITION: flatbuffers::VOffsetT = 10;
+  pub const VT_PUBLIC_KEY: flatbuffers::VOffsetT = 12;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-    Player { _tab: table }
+    ConnectResponse { _tab: table }
   }
   #[allow(unused_mut)]
   pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
     _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-    args: &'args PlayerArgs
-  ) -> flatbuffers::WIPOffset<Player<'bldr>> {
-    let mut builder = PlayerBuilder::new(_fbb);
-    builder.add_y(args.y);
-    builder.add_x(args.x);
-    builder.add_color(args.color);
+    args: &'args ConnectResponseArgs<'args>
+  ) -> flatbuffers::WIPOffset<ConnectResponse<'bldr>> {
+    let mut builder = ConnectResponseBuilder::new(_fbb);
+    builder.add_session_id(args.session_id);
+    if let Some(x) = args.public_key { builder.add_public_key(x); }
+    builder.add_queue_position(args.queue_position);
+    builder.add_reason(args.reason);
+    builder.add_status(args.status);
     builder.finish()
   }
 
 
   #[inline]
-  pub fn x(&self) -> f32 {
+  pub fn status(&self) -> ConnectStatus {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<f32>(Player::VT_X, Some(0.0)).unwrap()}
+    unsafe { self._tab.get::<ConnectStatus>(ConnectResponse::VT_STATUS, Some(ConnectStatus::Accepted)).unwrap()}
   }
   #[inline]
-  pub fn y(&self) -> f32 {
+  pub fn session_id(&self) -> u64 {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<f32>(Player::VT_Y, Some(0.0)).unwrap()}
+    unsafe { self._tab.get::<u64>(ConnectResponse::VT_SESSION_ID, Some(0)).unwrap()}
   }
   #[inline]
-  pub fn color(&self) -> Color {
+  pub fn reason(&self) -> RejectReason {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<Color>(Player::VT_COLOR, Some(Color::Red)).unwrap()}
+    unsafe { self._tab.get::<RejectReason>(ConnectResponse::VT_REASON, Some(RejectReason::None)).unwrap()}
+  }
+  #[inline]
+  pub fn queue_position(&self) -> u16 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u16>(ConnectResponse::VT_QUEUE_POSITION, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn public_key(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(ConnectResponse::VT_PUBLIC_KEY, None)}
   }
 }
 
-impl flatbuffers::Verifiable for Player<'_> {
+impl flatbuffers::Verifiable for ConnectResponse<'_> {
   #[inline]
   fn run_verifier(
     v: &mut flatbuffers::Verifier, pos: usize
   ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
     use self::flatbuffers::Verifiable;
     v.visit_table(pos)?
-     .visit_field::<f32>("x", Self::VT_X, false)?
-     .visit_field::<f32>("y", Self::VT_Y, false)?
-     .visit_field::<Color>("color", Self::VT_COLOR, false)?
+     .visit_field::<ConnectStatus>("status", Self::VT_STATUS, false)?
+     .visit_field::<u64>("session_id", Self::VT_SESSION_ID, false)?
+     .visit_field::<RejectReason>("reason", Self::VT_REASON, false)?
+     .visit_field::<u16>("queue_position", Self::VT_QUEUE_POSITION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("public_key", Self::VT_PUBLIC_KEY, false)?
      .finish();
     Ok(())
   }
 }
-pub struct PlayerArgs {
-    pub x: f32,
-    pub y: f32,
-    pub color: Color,
+pub struct ConnectResponseArgs<'a> {
+    pub status: ConnectStatus,
+    pub session_id: u64,
+    pub reason: RejectReason,
+    pub queue_position: u16,
+    pub public_key: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
 }
-impl<'a> Default for PlayerArgs {
+impl<'a> Default for ConnectResponseArgs<'a> {
   #[inline]
   fn default() -> Self {
-    PlayerArgs {
-      x: 0.0,
-      y: 0.0,
-      color: Color::Red,
+    ConnectResponseArgs {
+      status: ConnectStatus::Accepted,
+      session_id: 0,
+      reason: RejectReason::None,
+      queue_position: 0,
+      public_key: None,
     }
   }
 }
 
-pub struct PlayerBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+pub struct ConnectResponseBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PlayerBuilder<'a, 'b, A> {
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ConnectResponseBuilder<'a, 'b, A> {
   #[inline]
-  pub fn add_x(&mut self, x: f32) {
-    self.fbb_.push_slot::<f32>(Player::VT_X, x, 0.0);
+  pub fn add_status(&mut self, status: ConnectStatus) {
+    self.fbb_.push_slot::<ConnectStatus>(ConnectResponse::VT_STATUS, status, ConnectStatus::Accepted);
   }
   #[inline]
-  pub fn add_y(&mut self, y: f32) {
-    self.fbb_.push_slot::<f32>(Player::VT_Y, y, 0.0);
+  pub fn add_session_id(&mut self, session_id: u64) {
+    self.fbb_.push_slot::<u64>(ConnectResponse::VT_SESSION_ID, session_id, 0);
   }
   #[inline]
-  pub fn add_color(&mut self, color: Color) {
-    self.fbb_.push_slot::<Color>(Player::VT_COLOR, color, Color::Red);
+  pub fn add_reason(&mut self, reason: RejectReason) {
+    self.fbb_.push_slot::<RejectReason>(ConnectResponse::VT_REASON, reason, RejectReason::None);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PlayerBuilder<'a, 'b, A> {
+  pub fn add_queue_position(&mut self, queue_position: u16) {
+    self.fbb_.push_slot::<u16>(ConnectResponse::VT_QUEUE_POSITION, queue_position, 0);
+  }
+  #[inline]
+  pub fn add_public_key(&mut self, public_key: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ConnectResponse::VT_PUBLIC_KEY, public_key);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ConnectResponseBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
-    PlayerBuilder {
+    ConnectResponseBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<Player<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<ConnectResponse<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-impl core::fmt::Debug for Player<'_> {
+impl core::fmt::Debug for ConnectResponse<'_> {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-    let mut ds = f.debug_struct("Player");
-      ds.field("x", &self.x());
-      ds.field("y", &self.y());
-      ds.field("color", &self.color());
+    let mut ds = f.debug_struct("ConnectResponse");
+      ds.field("status", &self.status());
+      ds.field("session_id", &self.session_id());
+      ds.field("reason", &self.reason());
+      ds.field("queue_position", &self.queue_position());
+      ds.field("public_key", &self.public_key());
       ds.finish()
   }
 }
-pub enum PlayerCommandsOffset {}
+pub enum PlayerLeftOffset {}
 #[derive(Copy, Clone, PartialEq)]
 
-pub struct PlayerCommands<'a> {
+pub struct PlayerLeft<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for PlayerCommands<'a> {
-  type Inner = PlayerCommands<'a>;
+impl<'a> flatbuffers::Follow<'a> for PlayerLeft<'a> {
+  type Inner = PlayerLeft<'a>;
   #[inline]
   unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
     Self { _tab: flatbuffers::Table::new(buf, loc) }
   }
 }
 
-impl<'a> PlayerCommands<'a> {
-  pub const VT_COMMANDS: flatbuffers::VOffsetT = 4;
+impl<'a> PlayerLeft<'a> {
+  pub const VT_PLAYER_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_REASON: flatbuffers::VOffsetT = 6;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-    PlayerCommands { _tab: table }
+    PlayerLeft { _tab: table }
   }
   #[allow(unused_mut)]
   pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
     _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-    args: &'args PlayerCommandsArgs<'args>
-  ) -> flatbuffers::WIPOffset<PlayerCommands<'bldr>> {
-    let mut builder = PlayerCommandsBuilder::new(_fbb);
-    if let Some(x) = args.commands { builder.add_commands(x); }
+    args: &'args PlayerLeftArgs
+  ) -> flatbuffers::WIPOffset<PlayerLeft<'bldr>> {
+    let mut builder = PlayerLeftBuilder::new(_fbb);
+    builder.add_player_id(args.player_id);
+    builder.add_reason(args.reason);
     builder.finish()
   }
 
 
   #[inline]
-  pub fn commands(&self) -> Option<flatbuffers::Vector<'a, PlayerCommand>> {
+  pub fn player_id(&self) -> u32 {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, PlayerCommand>>>(PlayerCommands::VT_COMMANDS, None)}
+    unsafe { self._tab.get::<u32>(PlayerLeft::VT_PLAYER_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn reason(&self) -> LeaveReason {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<LeaveReason>(PlayerLeft::VT_REASON, Some(LeaveReason::Disconnected)).unwrap()}
   }
 }
 
-impl flatbuffers::Verifiable for PlayerCommands<'_> {
+impl flatbuffers::Verifiable for PlayerLeft<'_> {
   #[inline]
   fn run_verifier(
     v: &mut flatbuffers::Verifier, pos: usize
   ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
     use self::flatbuffers::Verifiable;
     v.visit_table(pos)?
-     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, PlayerCommand>>>("commands", Self::VT_COMMANDS, false)?
+     .visit_field::<u32>("player_id", Self::VT_PLAYER_ID, false)?
+     .visit_field::<LeaveReason>("reason", Self::VT_REASON, false)?
      .finish();
     Ok(())
   }
 }
-pub struct PlayerCommandsArgs<'a> {
-    pub commands: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, PlayerCommand>>>,
+pub struct PlayerLeftArgs {
+    pub player_id: u32,
+    pub reason: LeaveReason,
 }
-impl<'a> Default for PlayerCommandsArgs<'a> {
+impl<'a> Default for PlayerLeftArgs {
   #[inline]
   fn default() -> Self {
-    PlayerCommandsArgs {
-      commands: None,
+    PlayerLeftArgs {
+      player_id: 0,
+      reason: LeaveReason::Disconnected,
     }
   }
 }
 
-pub struct PlayerCommandsBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+pub struct PlayerLeftBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PlayerCommandsBuilder<'a, 'b, A> {
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PlayerLeftBuilder<'a, 'b, A> {
   #[inline]
-  pub fn add_commands(&mut self, commands: flatbuffers::WIPOffset<flatbuffers::Vector<'b , PlayerCommand>>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PlayerCommands::VT_COMMANDS, commands);
+  pub fn add_player_id(&mut self, player_id: u32) {
+    self.fbb_.push_slot::<u32>(PlayerLeft::VT_PLAYER_ID, player_id, 0);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PlayerCommandsBuilder<'a, 'b, A> {
+  pub fn add_reason(&mut self, reason: LeaveReason) {
+    self.fbb_.push_slot::<LeaveReason>(PlayerLeft::VT_REASON, reason, LeaveReason::Disconnected);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PlayerLeftBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
-    PlayerCommandsBuilder {
+    PlayerLeftBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<PlayerCommands<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<PlayerLeft<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-impl core::fmt::Debug for PlayerCommands<'_> {
+impl core::fmt::Debug for PlayerLeft<'_> {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-    let mut ds = f.debug_struct("PlayerCommands");
-      ds.field("commands", &self.commands());
+    let mut ds = f.debug_struct("PlayerLeft");
+      ds.field("player_id", &self.player_id());
+      ds.field("reason", &self.reason());
       ds.finish()
   }
 }
-pub enum PlayersListOffset {}
+pub enum ServerPacketOffset {}
 #[derive(Copy, Clone, PartialEq)]
 
-pub struct PlayersList<'a> {
+pub struct ServerPacket<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for PlayersList<'a> {
-  type Inner = PlayersList<'a>;
+impl<'a> flatbuffers::Follow<'a> for ServerPacket<'a> {
+  type Inner = ServerPacket<'a>;
   #[inline]
   unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
     Self { _tab: flatbuffers::Table::new(buf, loc) }
   }
 }
 
-impl<'a> PlayersList<'a> {
-  pub const VT_PLAYERS: flatbuffers::VOffsetT = 4;
+impl<'a> ServerPacket<'a> {
+  pub const VT_MESSAGE_TYPE: flatbuffers::VOffsetT = 4;
+  pub const VT_MESSAGE: flatbuffers::VOffsetT = 6;
+  pub const VT_PROTOCOL_VERSION: flatbuffers::VOffsetT = 8;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-    PlayersList { _tab: table }
+    ServerPacket { _tab: table }
   }
   #[allow(unused_mut)]
   pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
     _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-    args: &'args PlayersListArgs<'args>
-  ) -> flatbuffers::WIPOffset<PlayersList<'bldr>> {
-    let mut builder = PlayersListBuilder::new(_fbb);
-    if let Some(x) = args.players { builder.add_players(x); }
+    args: &'args ServerPacketArgs
+  ) -> flatbuffers::WIPOffset<ServerPacket<'bldr>> {
+    let mut builder = ServerPacketBuilder::new(_fbb);
+    if let Some(x) = args.message { builder.add_message(x); }
+    builder.add_protocol_version(args.protocol_version);
+    builder.add_message_type(args.message_type);
     builder.finish()
   }
 
 
   #[inline]
-  pub fn players(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Player<'a>>>> {
+  pub fn message_type(&self) -> ServerMessage {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Player>>>>(PlayersList::VT_PLAYERS, None)}
+    unsafe { self._tab.get::<ServerMessage>(ServerPacket::VT_MESSAGE_TYPE, Some(ServerMessage::NONE)).unwrap()}
+  }
+  #[inline]
+  pub fn message(&self) -> Option<flatbuffers::Table<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(ServerPacket::VT_MESSAGE, None)}
+  }
+  #[inline]
+  pub fn protocol_version(&self) -> u16 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u16>(ServerPacket::VT_PROTOCOL_VERSION, Some(0)).unwrap()}
+  }
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_players_list(&self) -> Option<PlayersList<'a>> {
+    if self.message_type() == ServerMessage::PlayersList {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { PlayersList::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_connect_response(&self) -> Option<ConnectResponse<'a>> {
+    if self.message_type() == ServerMessage::ConnectResponse {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { ConnectResponse::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_player_left(&self) -> Option<PlayerLeft<'a>> {
+    if self.message_type() == ServerMessage::PlayerLeft {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { PlayerLeft::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_players_delta(&self) -> Option<PlayersDelta<'a>> {
+    if self.message_type() == ServerMessage::PlayersDelta {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { PlayersDelta::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_fragment(&self) -> Option<Fragment<'a>> {
+    if self.message_type() == ServerMessage::Fragment {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Fragment::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_reliable(&self) -> Option<Reliable<'a>> {
+    if self.message_type() == ServerMessage::Reliable {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Reliable::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_reliable_ack(&self) -> Option<ReliableAck<'a>> {
+    if self.message_type() == ServerMessage::ReliableAck {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { ReliableAck::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_challenge(&self) -> Option<Challenge<'a>> {
+    if self.message_type() == ServerMessage::Challenge {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Challenge::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_pong(&self) -> Option<Pong<'a>> {
+    if self.message_type() == ServerMessage::Pong {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Pong::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_chat(&self) -> Option<Chat<'a>> {
+    if self.message_type() == ServerMessage::Chat {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Chat::init_from_table(t) }
+     })
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn message_as_path_challenge(&self) -> Option<PathChallenge<'a>> {
+    if self.message_type() == ServerMessage::PathChallenge {
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { PathChallenge::init_from_table(t) }
+     })
+    } else {
+      None
+    }
   }
 }
 
-impl flatbuffers::Verifiable for PlayersList<'_> {
+impl flatbuffers::Verifiable for ServerPacket<'_> {
   #[inline]
   fn run_verifier(
     v: &mut flatbuffers::Verifier, pos: usize
   ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
     use self::flatbuffers::Verifiable;
     v.visit_table(pos)?
-     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Player>>>>("players", Self::VT_PLAYERS, false)?
+     .visit_union::<ServerMessage, _>("message_type", Self::VT_MESSAGE_TYPE, "message", Self::VT_MESSAGE, false, |key, v, pos| {
+        match key {
+          ServerMessage::PlayersList => v.verify_union_variant::<flatbuffers::ForwardsUOffset<PlayersList>>("ServerMessage::PlayersList", pos),
+          ServerMessage::ConnectResponse => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ConnectResponse>>("ServerMessage::ConnectResponse", pos),
+          ServerMessage::PlayerLeft => v.verify_union_variant::<flatbuffers::ForwardsUOffset<PlayerLeft>>("ServerMessage::PlayerLeft", pos),
+          ServerMessage::PlayersDelta => v.verify_union_variant::<flatbuffers::ForwardsUOffset<PlayersDelta>>("ServerMessage::PlayersDelta", pos),
+          ServerMessage::Fragment => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Fragment>>("ServerMessage::Fragment", pos),
+          ServerMessage::Reliable => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Reliable>>("ServerMessage::Reliable", pos),
+          ServerMessage::ReliableAck => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ReliableAck>>("ServerMessage::ReliableAck", pos),
+          ServerMessage::Challenge => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Challenge>>("ServerMessage::Challenge", pos),
+          ServerMessage::Pong => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Pong>>("ServerMessage::Pong", pos),
+          ServerMessage::Chat => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Chat>>("ServerMessage::Chat", pos),
+          ServerMessage::PathChallenge => v.verify_union_variant::<flatbuffers::ForwardsUOffset<PathChallenge>>("ServerMessage::PathChallenge", pos),
+          _ => Ok(()),
+        }
+     })?
+     .visit_field::<u16>("protocol_version", Self::VT_PROTOCOL_VERSION, false)?
      .finish();
     Ok(())
   }
 }
-pub struct PlayersListArgs<'a> {
-    pub players: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Player<'a>>>>>,
+pub struct ServerPacketArgs {
+    pub message_type: ServerMessage,
+    pub message: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
+    pub protocol_version: u16,
 }
-impl<'a> Default for PlayersListArgs<'a> {
+impl<'a> Default for ServerPacketArgs {
   #[inline]
   fn default() -> Self {
-    PlayersListArgs {
-      players: None,
+    ServerPacketArgs {
+      message_type: ServerMessage::NONE,
+      message: None,
+      protocol_version: 0,
     }
   }
 }
 
-pub struct PlayersListBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+pub struct ServerPacketBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PlayersListBuilder<'a, 'b, A> {
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ServerPacketBuilder<'a, 'b, A> {
   #[inline]
-  pub fn add_players(&mut self, players: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Player<'b >>>>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PlayersList::VT_PLAYERS, players);
+  pub fn add_message_type(&mut self, message_type: ServerMessage) {
+    self.fbb_.push_slot::<ServerMessage>(ServerPacket::VT_MESSAGE_TYPE, message_type, ServerMessage::NONE);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PlayersListBuilder<'a, 'b, A> {
+  pub fn add_message(&mut self, message: flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ServerPacket::VT_MESSAGE, message);
+  }
+  #[inline]
+  pub fn add_protocol_version(&mut self, protocol_version: u16) {
+    self.fbb_.push_slot::<u16>(ServerPacket::VT_PROTOCOL_VERSION, protocol_version, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ServerPacketBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
-    PlayersListBuilder {
+    ServerPacketBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<PlayersList<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<ServerPacket<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-impl core::fmt::Debug for PlayersList<'_> {
+impl core::fmt::Debug for ServerPacket<'_> {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-    let mut ds = f.debug_struct("PlayersList");
-      ds.field("players", &self.players());
+    let mut ds = f.debug_struct("ServerPacket");
+      ds.field("message_type", &self.message_type());
+      match self.message_type() {
+        ServerMessage::PlayersList => {
+          if let Some(x) = self.message_as_players_list() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ServerMessage::ConnectResponse => {
+          if let Some(x) = self.message_as_connect_response() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ServerMessage::PlayerLeft => {
+          if let Some(x) = self.message_as_player_left() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ServerMessage::PlayersDelta => {
+          if let Some(x) = self.message_as_players_delta() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ServerMessage::Fragment => {
+          if let Some(x) = self.message_as_fragment() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ServerMessage::Reliable => {
+          if let Some(x) = self.message_as_reliable() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ServerMessage::ReliableAck => {
+          if let Some(x) = self.message_as_reliable_ack() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ServerMessage::Challenge => {
+          if let Some(x) = self.message_as_challenge() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ServerMessage::Pong => {
+          if let Some(x) = self.message_as_pong() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ServerMessage::Chat => {
+          if let Some(x) = self.message_as_chat() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        ServerMessage::PathChallenge => {
+          if let Some(x) = self.message_as_path_challenge() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        _ => {
+          let x: Option<()> = None;
+          ds.field("message", &x)
+        },
+      };
+      ds.field("protocol_version", &self.protocol_version());
       ds.finish()
   }
 }
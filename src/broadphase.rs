@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use crate::collision::Body;
+
+/// Uniform grid, hashed so the world needs no fixed bounds. Bodies are filed under every cell they
+/// touch, and only bodies sharing a cell are candidates for the narrow phase.
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl SpatialHash {
+    /// `cell_size` works best at around twice the size of a typical body.
+    pub fn new(cell_size: f32) -> SpatialHash {
+        SpatialHash {
+            cell_size: cell_size.max(1.0),
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Changes the cell size from the next call to `pairs` on.
+    pub fn set_cell_size(&mut self, cell_size: f32) {
+        let cell_size = cell_size.max(1.0);
+        if cell_size != self.cell_size {
+            self.cell_size = cell_size;
+            self.cells.clear();
+        }
+    }
+
+    fn cell_range(&self, body: &Body) -> ((i32, i32), (i32, i32)) {
+        let cell = |v: f32| (v / self.cell_size).floor() as i32;
+        ((cell(body.x), cell(body.y)), (cell(body.x + body.size), cell(body.y + body.size)))
+    }
+
+    /// Index pairs `(i, j)`, `i < j`, of bodies that share a cell, in order. Every overlapping pair
+    /// is among them, and no pair is listed twice.
+    pub fn pairs(&mut self, bodies: &[Body]) -> Vec<(usize, usize)> {
+        // Cells nobody was in last time are dropped, so bodies roaming the world don't leave a
+        // trail of empty cells to go through. The rest are emptied rather than dropped, since
+        // bodies move only a little from call to call and are likely to fill them again.
+        self.cells.retain(|_, members| !members.is_empty());
+        self.cells.values_mut().for_each(Vec::clear);
+        for (index, body) in bodies.iter().enumerate() {
+            let ((min_x, min_y), (max_x, max_y)) = self.cell_range(body);
+            for x in min_x..=max_x {
+                for y in min_y..=max_y {
+                    self.cells.entry((x, y)).or_default().push(index);
+                }
+            }
+        }
+
+        let mut pairs = vec![];
+        for (&(x, y), members) in &self.cells {
+            for (n, &i) in members.iter().enumerate() {
+                for &j in &members[n + 1..] {
+                    // Two bodies can share several cells. Only the one at the top-left corner of
+                    // the cells they share reports them.
+                    let ((a_x, a_y), _) = self.cell_range(&bodies[i]);
+                    let ((b_x, b_y), _) = self.cell_range(&bodies[j]);
+                    if (a_x.max(b_x), a_y.max(b_y)) == (x, y) {
+                        pairs.push((i.min(j), i.max(j)));
+                    }
+                }
+            }
+        }
+        // Sorted so the narrow phase runs in the same order whatever the hash map's order was.
+        pairs.sort_unstable();
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(x: f32, y: f32, size: f32) -> Body {
+        Body { x, y, vel_x: 0.0, vel_y: 0.0, size, grounded: false }
+    }
+
+    fn crowd(count: usize) -> Vec<Body> {
+        let mut state = 0x2545f4914f6cdd1du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 10_000) as f32 / 10_000.0
+        };
+        (0..count).map(|_| body(next() * 400.0, next() * 400.0, 4.0 + next() * 40.0)).collect()
+    }
+
+    #[test]
+    fn finds_every_overlapping_pair_exactly_once() {
+        let bodies = crowd(300);
+        let mut pairs = SpatialHash::new(32.0).pairs(&bodies);
+        let count = pairs.len();
+        pairs.dedup();
+        assert_eq!(pairs.len(), count);
+
+        for i in 0..bodies.len() {
+            for j in i + 1..bodies.len() {
+                let (x, y) = bodies[i].penetration(&bodies[j]);
+                if x > 0.0 && y > 0.0 {
+                    assert!(pairs.binary_search(&(i, j)).is_ok(), "missed {} and {}", i, j);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn distant_bodies_are_not_paired() {
+        let bodies = [body(0.0, 0.0, 8.0), body(100.0, 0.0, 8.0), body(-100.0, -100.0, 8.0)];
+        assert!(SpatialHash::new(16.0).pairs(&bodies).is_empty());
+    }
+
+    #[test]
+    fn a_body_larger_than_a_cell_is_paired_once() {
+        let bodies = [body(0.0, 0.0, 100.0), body(50.0, 50.0, 100.0)];
+        let mut grid = SpatialHash::new(10.0);
+        assert_eq!(grid.pairs(&bodies), vec![(0, 1)]);
+        // Reusing the grid doesn't remember the previous bodies.
+        assert!(grid.pairs(&bodies[..1]).is_empty());
+    }
+
+    #[test]
+    fn cells_left_empty_are_dropped() {
+        let mut grid = SpatialHash::new(10.0);
+        grid.pairs(&[body(0.0, 0.0, 5.0)]);
+        grid.pairs(&[body(1000.0, 1000.0, 5.0)]);
+        // Emptied by the second call, gone after the third.
+        grid.pairs(&[body(1000.0, 1000.0, 5.0)]);
+        assert_eq!(grid.cells.len(), 1);
+        assert!(grid.cells.contains_key(&(100, 100)));
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+// Offsets are taken from the fastest of this many recent exchanges, since it queued the least.
+const OFFSET_WINDOW: usize = 8;
+// Anything slower than this is a stale or made-up exchange rather than a slow link.
+const MAX_ROUND_TRIP_MICROS: u64 = 5_000_000;
+// Gains as in TCP's RTT estimator (RFC 6298) and RTP's jitter estimate (RFC 3550).
+const RTT_GAIN: f64 = 1.0 / 8.0;
+const JITTER_GAIN: f64 = 1.0 / 16.0;
+const OFFSET_GAIN: f64 = 1.0 / 4.0;
+
+/// One request/response exchange, as four timestamps in microseconds. `sent` and `received` are
+/// on the local clock, `remote_received` and `remote_sent` on the other end's.
+#[derive(Clone, Copy, Debug)]
+pub struct ClockSample {
+    pub sent: u64,
+    pub remote_received: u64,
+    pub remote_sent: u64,
+    pub received: u64,
+}
+
+impl ClockSample {
+    /// Time spent on the wire, leaving out however long the other end held the message.
+    pub fn round_trip(&self) -> Option<u64> {
+        let total = self.received.checked_sub(self.sent)?;
+        let held = self.remote_sent.checked_sub(self.remote_received)?;
+        total.checked_sub(held)
+    }
+
+    /// Remote clock minus local clock, assuming both legs took equally long.
+    pub fn offset(&self) -> i64 {
+        let outbound = self.remote_received as i128 - self.sent as i128;
+        let inbound = self.remote_sent as i128 - self.received as i128;
+        ((outbound + inbound) / 2).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+}
+
+/// Running estimate of round-trip time, jitter and clock offset to one peer.
+///
+/// The server keeps one per player; a client keeps one for the server and feeds it its Pongs,
+/// then uses `to_local` to place snapshot `server_time`s on its own clock.
+pub struct ClockSync {
+    rtt: Option<f64>,
+    jitter: f64,
+    offset: Option<f64>,
+    last_rtt: Option<u64>,
+    recent: VecDeque<(u64, i64)>,
+    samples: u64,
+}
+
+impl ClockSync {
+    pub fn new() -> ClockSync {
+        ClockSync {
+            rtt: None,
+            jitter: 0.0,
+            offset: None,
+            last_rtt: None,
+            recent: VecDeque::with_capacity(OFFSET_WINDOW),
+            samples: 0,
+        }
+    }
+
+    /// Folds in one exchange. Returns false, and changes nothing, if its timestamps can't be right.
+    pub fn observe(&mut self, sample: ClockSample) -> bool {
+        let Some(rtt) = sample.round_trip().filter(|rtt| *rtt <= MAX_ROUND_TRIP_MICROS) else {
+            return false;
+        };
+        self.samples += 1;
+
+        if let Some(last) = self.last_rtt {
+            let variation = rtt.abs_diff(last) as f64;
+            self.jitter += (variation - self.jitter) * JITTER_GAIN;
+        }
+        self.last_rtt = Some(rtt);
+        self.rtt = Some(match self.rtt {
+            Some(smoothed) => smoothed + (rtt as f64 - smoothed) * RTT_GAIN,
+            None => rtt as f64,
+        });
+
+        if self.recent.len() == OFFSET_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((rtt, sample.offset()));
+        let (_, best) = *self.recent.iter().min_by_key(|(rtt, _)| *rtt).unwrap();
+        self.offset = Some(match self.offset {
+            Some(smoothed) => smoothed + (best as f64 - smoothed) * OFFSET_GAIN,
+            None => best as f64,
+        });
+        true
+    }
+
+    /// Smoothed round-trip time, once there has been at least one exchange.
+    pub fn rtt(&self) -> Option<Duration> {
+        self.rtt.map(|micros| Duration::from_micros(micros as u64))
+    }
+
+    /// Mean variation between consecutive round trips.
+    pub fn jitter(&self) -> Duration {
+        Duration::from_micros(self.jitter as u64)
+    }
+
+    /// Remote clock minus local clock, in microseconds.
+    pub fn offset(&self) -> Option<i64> {
+        self.offset.map(|micros| micros.round() as i64)
+    }
+
+    /// How many exchanges have been accepted so far.
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Converts a local timestamp to the remote clock.
+    pub fn to_remote(&self, local: u64) -> Option<u64> {
+        Some(local.saturating_add_signed(self.offset()?))
+    }
+
+    /// Converts a remote timestamp to the local clock.
+    pub fn to_local(&self, remote: u64) -> Option<u64> {
+        Some(remote.saturating_add_signed(-self.offset()?))
+    }
+}
+
+impl Default for ClockSync {
+    fn default() -> ClockSync {
+        ClockSync::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The remote clock runs `offset` ahead; each leg takes `out` and `back` microseconds.
+    fn exchange(at: u64, offset: i64, out: u64, back: u64) -> ClockSample {
+        let remote_received = (at + out).saturating_add_signed(offset);
+        ClockSample {
+            sent: at,
+            remote_received,
+            remote_sent: remote_received + 100,
+            received: at + out + 100 + back,
+        }
+    }
+
+    #[test]
+    fn symmetric_link_gives_exact_rtt_and_offset() {
+        let mut clock = ClockSync::new();
+        for i in 0..20 {
+            assert!(clock.observe(exchange(1_000_000 + i * 50_000, 250_000, 20_000, 20_000)));
+        }
+        assert_eq!(clock.rtt(), Some(Duration::from_millis(40)));
+        assert_eq!(clock.jitter(), Duration::ZERO);
+        assert_eq!(clock.offset(), Some(250_000));
+        assert_eq!(clock.to_remote(1_000), Some(251_000));
+        assert_eq!(clock.to_local(251_000), Some(1_000));
+    }
+
+    #[test]
+    fn offset_ignores_queueing_spikes() {
+        let mut clock = ClockSync::new();
+        for i in 0..8 {
+            clock.observe(exchange(i * 50_000, -3_000, 10_000, 10_000));
+        }
+        // A burst of packets that sat in a queue on the way back would skew a naive average.
+        for i in 8..14 {
+            clock.observe(exchange(i * 50_000, -3_000, 10_000, 90_000));
+        }
+        assert_eq!(clock.offset(), Some(-3_000));
+        assert!(clock.rtt().unwrap() > Duration::from_millis(20));
+        assert!(clock.jitter() > Duration::ZERO);
+    }
+
+    #[test]
+    fn impossible_samples_are_ignored() {
+        let mut clock = ClockSync::new();
+        let backwards = ClockSample { sent: 10, remote_received: 0, remote_sent: 0, received: 5 };
+        let held_too_long = ClockSample { sent: 0, remote_received: 0, remote_sent: 1_000, received: 500 };
+        let ancient = ClockSample { sent: 0, remote_received: 0, remote_sent: 0, received: 60_000_000 };
+        assert!(!clock.observe(backwards));
+        assert!(!clock.observe(held_too_long));
+        assert!(!clock.observe(ancient));
+        assert_eq!(clock.rtt(), None);
+        assert_eq!(clock.samples(), 0);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::broadphase::SpatialHash;
+
+// Later passes fix up overlaps that earlier ones caused, e.g. further up a stack of players.
+const PASSES: usize = 4;
+// Below this many bodies checking every pair beats going through the grid. `cargo bench` puts
+// the crossover at 50 to 100 bodies.
+const ALL_PAIRS_BELOW: usize = 64;
+
+/// A square body as collision sees it. `y` grows downwards, as on screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Body {
+    pub x: f32,
+    pub y: f32,
+    pub vel_x: f32,
+    pub vel_y: f32,
+    pub size: f32,
+    /// Resting on something that holds it up: the ground, or another grounded body.
+    pub grounded: bool,
+}
+
+impl Body {
+    /// Bigger bodies are heavier, in proportion to their area.
+    pub fn mass(&self) -> f32 {
+        self.size * self.size
+    }
+
+    /// How far this body and `other` overlap along x and y. Both are positive when they overlap;
+    /// touching edges don't count.
+    pub fn penetration(&self, other: &Body) -> (f32, f32) {
+        let x = (self.x + self.size).min(other.x + other.size) - self.x.max(other.x);
+        let y = (self.y + self.size).min(other.y + other.size) - self.y.max(other.y);
+        (x, y)
+    }
+}
+
+/// Pushes overlapping bodies apart along the axis they overlap least on, each moving in
+/// proportion to the other's mass. Bodies that were closing in on each other end up with their
+/// combined momentum along that axis. A body standing on a grounded one becomes grounded and
+/// takes all of the correction, so stacks don't sink.
+///
+/// Kept from tick to tick, so the grid's cells are only allocated once.
+pub struct Resolver {
+    grid: SpatialHash,
+    all_pairs_below: usize,
+}
+
+impl Default for Resolver {
+    fn default() -> Resolver {
+        Resolver::new()
+    }
+}
+
+impl Resolver {
+    pub fn new() -> Resolver {
+        Resolver::with_threshold(ALL_PAIRS_BELOW)
+    }
+
+    /// Checks every pair when there are fewer than `all_pairs_below` bodies, and only the pairs
+    /// the grid finds otherwise.
+    pub fn with_threshold(all_pairs_below: usize) -> Resolver {
+        Resolver {
+            grid: SpatialHash::new(1.0),
+            all_pairs_below,
+        }
+    }
+
+    /// `confine` keeps a body inside the world and should set `grounded` when it lands on the
+    /// floor. It runs before the first pass and after every pass.
+    pub fn resolve(&mut self, bodies: &mut [Body], mut confine: impl FnMut(&mut Body)) {
+        bodies.iter_mut().for_each(&mut confine);
+        if bodies.len() < 2 {
+            return;
+        }
+        let use_grid = bodies.len() >= self.all_pairs_below;
+        if use_grid {
+            let mean_size = bodies.iter().map(|b| b.size).sum::<f32>() / bodies.len() as f32;
+            self.grid.set_cell_size(2.0 * mean_size);
+        }
+        for _ in 0..PASSES {
+            let mut separated = false;
+            if use_grid {
+                // Bodies moved in the last pass, so the candidates are found again each time.
+                for (i, j) in self.grid.pairs(bodies) {
+                    let (head, tail) = bodies.split_at_mut(j);
+                    separated |= separate(&mut head[i], &mut tail[0]);
+                }
+            } else {
+                // In the same order as the grid's pairs, so both ways give the same result.
+                for i in 0..bodies.len() {
+                    for j in i + 1..bodies.len() {
+                        let (head, tail) = bodies.split_at_mut(j);
+                        separated |= separate(&mut head[i], &mut tail[0]);
+                    }
+                }
+            }
+            bodies.iter_mut().for_each(&mut confine);
+            if !separated {
+                break;
+            }
+        }
+    }
+}
+
+/// Resolves one pair. Returns whether they overlapped.
+pub fn separate(a: &mut Body, b: &mut Body) -> bool {
+    let (overlap_x, overlap_y) = a.penetration(b);
+    if overlap_x <= 0.0 || overlap_y <= 0.0 {
+        return false;
+    }
+
+    if overlap_y < overlap_x {
+        let (upper, lower) = if a.y + a.size / 2.0 <= b.y + b.size / 2.0 { (a, b) } else { (b, a) };
+        // Something already holds the lower body up, so it can't be pushed down.
+        let upper_share = if lower.grounded { 1.0 } else { lower.mass() / (upper.mass() + lower.mass()) };
+        upper.y -= overlap_y * upper_share;
+        lower.y += overlap_y * (1.0 - upper_share);
+        if upper.vel_y > lower.vel_y {
+            if lower.grounded {
+                upper.vel_y = lower.vel_y;
+            } else {
+                let vel_y = shared_velocity(upper.mass(), upper.vel_y, lower.mass(), lower.vel_y);
+                upper.vel_y = vel_y;
+                lower.vel_y = vel_y;
+            }
+        }
+        upper.grounded |= lower.grounded;
+    } else {
+        let (left, right) = if a.x + a.size / 2.0 <= b.x + b.size / 2.0 { (a, b) } else { (b, a) };
+        let left_share = right.mass() / (left.mass() + right.mass());
+        left.x -= overlap_x * left_share;
+        right.x += overlap_x * (1.0 - left_share);
+        if left.vel_x > right.vel_x {
+            let vel_x = shared_velocity(left.mass(), left.vel_x, right.mass(), right.vel_x);
+            left.vel_x = vel_x;
+            right.vel_x = vel_x;
+        }
+    }
+    true
+}
+
+/// Velocity of two bodies that stick together, which keeps their total momentum.
+fn shared_velocity(mass_a: f32, vel_a: f32, mass_b: f32, vel_b: f32) -> f32 {
+    (mass_a * vel_a + mass_b * vel_b) / (mass_a + mass_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLOOR: f32 = 100.0;
+
+    fn body(x: f32, y: f32, size: f32) -> Body {
+        Body { x, y, vel_x: 0.0, vel_y: 0.0, size, grounded: false }
+    }
+
+    fn floor(body: &mut Body) {
+        if body.y + body.size >= FLOOR {
+            body.y = FLOOR - body.size;
+            body.vel_y = body.vel_y.min(0.0);
+            body.grounded = true;
+        }
+    }
+
+    /// Resolves with the grid and by checking every pair, which should come out the same.
+    fn resolve_both_ways(bodies: &mut [Body], confine: impl FnMut(&mut Body) + Copy) {
+        let mut by_grid = bodies.to_vec();
+        Resolver::with_threshold(0).resolve(&mut by_grid, confine);
+        Resolver::with_threshold(usize::MAX).resolve(bodies, confine);
+        assert_eq!(by_grid, bodies);
+    }
+
+    #[test]
+    fn players_stand_on_each_other() {
+        let bottom = body(0.0, FLOOR - 16.0, 16.0);
+        let middle = Body { vel_y: 50.0, ..body(4.0, FLOOR - 30.0, 16.0) };
+        let top = Body { vel_y: 80.0, ..body(2.0, FLOOR - 45.0, 16.0) };
+        let mut bodies = [top, middle, bottom];
+        resolve_both_ways(&mut bodies, floor);
+
+        let [top, middle, bottom] = bodies;
+        assert_eq!(bottom.y, FLOOR - 16.0);
+        assert_eq!(middle.y, FLOOR - 32.0);
+        assert_eq!(top.y, FLOOR - 48.0);
+        assert!(bodies.iter().all(|b| b.grounded && b.vel_y == 0.0));
+    }
+
+    #[test]
+    fn sideways_pushes_conserve_momentum() {
+        let big = Body { vel_x: 30.0, ..body(0.0, 0.0, 32.0) };
+        let small = Body { vel_x: -10.0, ..body(30.0, 8.0, 16.0) };
+        let momentum = big.mass() * big.vel_x + small.mass() * small.vel_x;
+        let mut bodies = [big, small];
+        resolve_both_ways(&mut bodies, |_| {});
+
+        let [big, small] = bodies;
+        assert_eq!(big.mass() * big.vel_x + small.mass() * small.vel_x, momentum);
+        assert_eq!(big.vel_x, small.vel_x);
+        assert!(big.vel_x > 0.0);
+        // The small one gives way four times as much as the big one.
+        assert_eq!(big.x, -0.4);
+        assert_eq!(small.x, 31.6);
+        assert_eq!(big.penetration(&small).0, 0.0);
+    }
+
+    #[test]
+    fn bodies_in_mid_air_share_a_vertical_collision() {
+        let falling = Body { vel_y: 20.0, ..body(0.0, 0.0, 16.0) };
+        let rising = Body { vel_y: -20.0, ..body(0.0, 12.0, 16.0) };
+        let mut bodies = [falling, rising];
+        resolve_both_ways(&mut bodies, |_| {});
+        assert_eq!(bodies[0].y, -2.0);
+        assert_eq!(bodies[1].y, 14.0);
+        assert_eq!(bodies[0].vel_y, 0.0);
+        assert!(!bodies[0].grounded);
+    }
+
+    #[test]
+    fn touching_is_not_overlapping() {
+        let mut a = body(0.0, 0.0, 16.0);
+        let mut b = Body { vel_x: -5.0, ..body(16.0, 0.0, 16.0) };
+        assert!(!separate(&mut a, &mut b));
+        assert_eq!(b.vel_x, -5.0);
+    }
+}
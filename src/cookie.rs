@@ -0,0 +1,133 @@
+use std::io;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::crypto::{constant_time_eq, hmac_sha256, random_bytes, DIGEST_SIZE};
+
+/// Issue time followed by the HMAC over it and the client's address.
+pub const COOKIE_SIZE: usize = 8 + DIGEST_SIZE;
+// Long enough for one round trip and a retry, short enough that a captured cookie goes stale fast.
+const COOKIE_LIFETIME: Duration = Duration::from_secs(10);
+// The previous secret stays valid for one more rotation so cookies issued just before it still work.
+const SECRET_ROTATION: Duration = Duration::from_secs(60);
+const SECRET_SIZE: usize = 32;
+
+/// Issues and checks stateless join cookies.
+///
+/// A cookie proves the client can receive packets at the address it claims, without the server
+/// remembering anything about it until it comes back with one.
+pub struct CookieJar {
+    secret: [u8; SECRET_SIZE],
+    previous_secret: [u8; SECRET_SIZE],
+    rotated_at: Instant,
+}
+
+impl CookieJar {
+    pub fn new() -> io::Result<CookieJar> {
+        let mut secret = [0u8; SECRET_SIZE];
+        random_bytes(&mut secret)?;
+        let mut previous_secret = [0u8; SECRET_SIZE];
+        random_bytes(&mut previous_secret)?;
+        Ok(CookieJar {
+            secret,
+            previous_secret,
+            rotated_at: Instant::now(),
+        })
+    }
+
+    fn rotate_if_due(&mut self) {
+        if self.rotated_at.elapsed() < SECRET_ROTATION {
+            return;
+        }
+        let mut secret = [0u8; SECRET_SIZE];
+        // Keeping the current secret a while longer is better than failing; rotation is retried
+        // on the next cookie.
+        if let Err(e) = random_bytes(&mut secret) {
+            println!("Can't rotate the cookie secret: {}", e);
+            return;
+        }
+        self.previous_secret = self.secret;
+        self.secret = secret;
+        self.rotated_at = Instant::now();
+    }
+
+    pub fn issue(&mut self, addr: &SocketAddr) -> [u8; COOKIE_SIZE] {
+        self.rotate_if_due();
+        let issued = unix_time().to_le_bytes();
+        let mut cookie = [0u8; COOKIE_SIZE];
+        cookie[..8].copy_from_slice(&issued);
+        cookie[8..].copy_from_slice(&mac(&self.secret, &issued, addr));
+        cookie
+    }
+
+    pub fn verify(&mut self, addr: &SocketAddr, cookie: &[u8]) -> bool {
+        self.rotate_if_due();
+        if cookie.len() != COOKIE_SIZE {
+            return false;
+        }
+        let (issued, tag) = cookie.split_at(8);
+        let issued_at = u64::from_le_bytes(issued.try_into().unwrap());
+        let now = unix_time();
+        if issued_at > now || now - issued_at > COOKIE_LIFETIME.as_secs() {
+            return false;
+        }
+        constant_time_eq(tag, &mac(&self.secret, issued, addr))
+            || constant_time_eq(tag, &mac(&self.previous_secret, issued, addr))
+    }
+}
+
+fn mac(secret: &[u8], issued: &[u8], addr: &SocketAddr) -> [u8; DIGEST_SIZE] {
+    let ip = match addr {
+        SocketAddr::V4(v4) => v4.ip().to_ipv6_mapped().octets(),
+        SocketAddr::V6(v6) => v6.ip().octets(),
+    };
+    hmac_sha256(secret, &[issued, &ip, &addr.port().to_le_bytes()])
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cookie_verifies_for_its_own_address_only() {
+        let mut jar = CookieJar::new().unwrap();
+        let addr: SocketAddr = "127.0.0.1:9001".parse().unwrap();
+        let cookie = jar.issue(&addr);
+        assert!(jar.verify(&addr, &cookie));
+        assert!(!jar.verify(&"127.0.0.1:9002".parse().unwrap(), &cookie));
+        assert!(!jar.verify(&"127.0.0.2:9001".parse().unwrap(), &cookie));
+    }
+
+    #[test]
+    fn tampered_or_truncated_cookie_is_rejected() {
+        let mut jar = CookieJar::new().unwrap();
+        let addr: SocketAddr = "[::1]:9001".parse().unwrap();
+        let mut cookie = jar.issue(&addr);
+        assert!(!jar.verify(&addr, &cookie[..COOKIE_SIZE - 1]));
+        cookie[COOKIE_SIZE - 1] ^= 1;
+        assert!(!jar.verify(&addr, &cookie));
+        assert!(!jar.verify(&addr, &[]));
+    }
+
+    #[test]
+    fn cookie_survives_one_rotation_and_then_expires() {
+        let mut jar = CookieJar::new().unwrap();
+        let addr: SocketAddr = "127.0.0.1:9001".parse().unwrap();
+        let cookie = jar.issue(&addr);
+        jar.rotated_at -= SECRET_ROTATION;
+        assert!(jar.verify(&addr, &cookie));
+        jar.rotated_at -= SECRET_ROTATION;
+        assert!(!jar.verify(&addr, &cookie));
+
+        let mut stale = jar.issue(&addr);
+        let issued = unix_time() - COOKIE_LIFETIME.as_secs() - 1;
+        stale[..8].copy_from_slice(&issued.to_le_bytes());
+        let tag = mac(&jar.secret, &stale[..8], &addr);
+        stale[8..].copy_from_slice(&tag);
+        assert!(!jar.verify(&addr, &stale));
+    }
+}
@@ -0,0 +1,46 @@
+//! ChaCha20-Poly1305 AEAD (RFC 8439), from the `chacha20poly1305` crate.
+
+use ::chacha20poly1305::aead::AeadInPlace;
+use ::chacha20poly1305::{ChaCha20Poly1305, KeyInit, Tag};
+
+pub const KEY_SIZE: usize = 32;
+pub const NONCE_SIZE: usize = 12;
+pub const TAG_SIZE: usize = 16;
+
+/// Encrypts `buf` in place and returns the tag that authenticates it together with `aad`.
+pub fn seal(key: &[u8; KEY_SIZE], nonce: &[u8; NONCE_SIZE], aad: &[u8], buf: &mut [u8]) -> [u8; TAG_SIZE] {
+    // Only fails for messages of hundreds of gigabytes.
+    let tag = ChaCha20Poly1305::new(key.into()).encrypt_in_place_detached(nonce.into(), aad, buf).unwrap();
+    tag.into()
+}
+
+/// Checks `tag` and decrypts `buf` in place. On failure `buf` is left as it was.
+pub fn open(key: &[u8; KEY_SIZE], nonce: &[u8; NONCE_SIZE], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> bool {
+    if tag.len() != TAG_SIZE {
+        return false;
+    }
+    ChaCha20Poly1305::new(key.into())
+        .decrypt_in_place_detached(nonce.into(), aad, buf, Tag::from_slice(tag))
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tampering_is_detected_and_leaves_the_buffer_alone() {
+        let key = [7; KEY_SIZE];
+        let nonce = [3; NONCE_SIZE];
+        let mut buf = *b"sealed message";
+        let tag = seal(&key, &nonce, b"header", &mut buf);
+        let sealed = buf;
+        assert_ne!(&sealed, b"sealed message");
+
+        assert!(!open(&key, &nonce, b"other header", &mut buf, &tag));
+        assert_eq!(buf, sealed);
+        assert!(!open(&key, &nonce, b"header", &mut buf, &tag[..8]));
+        assert!(open(&key, &nonce, b"header", &mut buf, &tag));
+        assert_eq!(&buf, b"sealed message");
+    }
+}
@@ -0,0 +1,34 @@
+//! X25519 Diffie-Hellman (RFC 7748), from the `x25519-dalek` crate.
+
+use std::io;
+
+use x25519_dalek::X25519_BASEPOINT_BYTES;
+
+use super::random_bytes;
+
+pub const KEY_SIZE: usize = 32;
+
+pub fn x25519(scalar: &[u8; KEY_SIZE], point: &[u8; KEY_SIZE]) -> [u8; KEY_SIZE] {
+    x25519_dalek::x25519(*scalar, *point)
+}
+
+/// A fresh secret key and its public key.
+pub fn generate_keypair() -> io::Result<([u8; KEY_SIZE], [u8; KEY_SIZE])> {
+    let mut secret = [0u8; KEY_SIZE];
+    random_bytes(&mut secret)?;
+    let public = x25519(&secret, &X25519_BASEPOINT_BYTES);
+    Ok((secret, public))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_sides_agree_on_the_shared_secret() {
+        let (alice, alice_public) = generate_keypair().unwrap();
+        let (bob, bob_public) = generate_keypair().unwrap();
+        assert_ne!(alice_public, bob_public);
+        assert_eq!(x25519(&alice, &bob_public), x25519(&bob, &alice_public));
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use flatbuffers::FlatBufferBuilder;
+
+use crate::protocol::finish_server_packet;
+use crate::schema_generated::{Fragment, FragmentArgs, ServerMessage};
+use crate::secure::{self, Sealer};
+
+/// Largest datagram we send. Stays under the IPv6 minimum MTU with room for IP/UDP headers.
+pub const MAX_DATAGRAM_SIZE: usize = 1200;
+// Room for sealing is kept whether or not the peer is secure, so both send the same packets.
+const MAX_UNSEALED_SIZE: usize = MAX_DATAGRAM_SIZE - secure::OVERHEAD;
+// Leaves room for the ServerPacket and Fragment tables wrapped around each chunk.
+const FRAGMENT_PAYLOAD: usize = MAX_UNSEALED_SIZE - 64;
+const MAX_FRAGMENTS: u16 = 64;
+// Per source address, so one client sending half messages can only push out its own.
+const MAX_PARTIAL_MESSAGES: usize = 8;
+// A message still missing fragments after this long is assumed lost.
+const FRAGMENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Sends a finished ServerPacket, splitting it into Fragment packets if it is too large for one datagram.
+/// With a `sealer`, each datagram is sealed after splitting.
+pub fn send_to(socket: &UdpSocket,
+               addr: &SocketAddr,
+               packet: &[u8],
+               message_id: u16,
+               sealer: Option<&Sealer>) -> io::Result<()> {
+    if packet.len() <= MAX_UNSEALED_SIZE {
+        return secure::send_to(socket, addr, packet, sealer);
+    }
+    for datagram in server_fragments(packet, message_id) {
+        secure::send_to(socket, addr, &datagram, sealer)?;
+    }
+    Ok(())
+}
+
+/// Splits a finished ServerPacket into ServerPackets each carrying one numbered Fragment.
+/// Packets too large for `MAX_FRAGMENTS` fragments are dropped, like any other lost datagram.
+pub fn server_fragments(packet: &[u8], message_id: u16) -> Vec<Vec<u8>> {
+    let count = packet.len().div_ceil(FRAGMENT_PAYLOAD);
+    if count > MAX_FRAGMENTS as usize {
+        return vec![];
+    }
+
+    let mut builder = FlatBufferBuilder::with_capacity(MAX_DATAGRAM_SIZE);
+    packet
+        .chunks(FRAGMENT_PAYLOAD)
+        .enumerate()
+        .map(|(index, chunk)| {
+            builder.reset();
+            let data = builder.create_vector(chunk);
+            let fragment = Fragment::create(
+                &mut builder,
+                &FragmentArgs {
+                    message_id,
+                    index: index as u16,
+                    count: count as u16,
+                    data: Some(data),
+                },
+            );
+            finish_server_packet(&mut builder, ServerMessage::Fragment, fragment.as_union_value());
+            builder.finished_data().to_vec()
+        })
+        .collect()
+}
+
+struct PartialMessage {
+    chunks: Vec<Option<Vec<u8>>>,
+    received: usize,
+    started: Instant,
+}
+
+/// Collects Fragment packets until a whole message is available.
+///
+/// Fragments may arrive duplicated or out of order. Messages that never complete are discarded
+/// after `FRAGMENT_TIMEOUT`, and at most `MAX_PARTIAL_MESSAGES` per source are buffered at once.
+pub struct Reassembler {
+    partial: HashMap<(SocketAddr, u16), PartialMessage>,
+}
+
+impl Reassembler {
+    pub fn new() -> Reassembler {
+        Reassembler { partial: HashMap::new() }
+    }
+
+    /// Stores one fragment and returns the reassembled message once its last fragment arrives.
+    pub fn insert(&mut self, source: SocketAddr, fragment: &Fragment) -> Option<Vec<u8>> {
+        let count = fragment.count();
+        let index = fragment.index();
+        let data = fragment.data()?;
+        if count == 0 || count > MAX_FRAGMENTS || index >= count {
+            return None;
+        }
+
+        self.partial.retain(|_, m| m.started.elapsed() < FRAGMENT_TIMEOUT);
+        let key = (source, fragment.message_id());
+        if !self.partial.contains_key(&key) {
+            let from_source = self.partial.iter().filter(|((addr, _), _)| *addr == source);
+            if from_source.clone().count() >= MAX_PARTIAL_MESSAGES {
+                let oldest = from_source.min_by_key(|(_, m)| m.started).map(|(k, _)| *k)?;
+                self.partial.remove(&oldest);
+            }
+        }
+
+        let message = self.partial.entry(key).or_insert_with(|| PartialMessage {
+            chunks: vec![None; count as usize],
+            received: 0,
+            started: Instant::now(),
+        });
+        if message.chunks.len() != count as usize {
+            // Disagrees with the fragments we already have, so the message can't be trusted.
+            self.partial.remove(&key);
+            return None;
+        }
+        let slot = &mut message.chunks[index as usize];
+        if slot.is_none() {
+            *slot = Some(data.bytes().to_vec());
+            message.received += 1;
+        }
+        if message.received < message.chunks.len() {
+            return None;
+        }
+
+        let message = self.partial.remove(&key)?;
+        Some(message.chunks.into_iter().flatten().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema_generated::{Color, Player, PlayerArgs, PlayersList, PlayersListArgs, ServerPacket};
+    use flatbuffers::root;
+
+    const SOURCE: &str = "127.0.0.1:9001";
+    const OTHER_SOURCE: &str = "127.0.0.1:9002";
+
+    fn snapshot(player_count: u32) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::new();
+        let players: Vec<_> = (0..player_count)
+            .map(|id| {
+                let args = PlayerArgs { id, x: id as f32, y: 1.0, color: Color::Blue, aim_x: 0.0, aim_y: 1.0 };
+                Player::create(&mut builder, &args)
+            })
+            .collect();
+        let players = builder.create_vector(&players);
+        let list = PlayersList::create(&mut builder, &PlayersListArgs { players: Some(players), ..Default::default() });
+        finish_server_packet(&mut builder, ServerMessage::PlayersList, list.as_union_value());
+        builder.finished_data().to_vec()
+    }
+
+    fn feed(reassembler: &mut Reassembler, datagram: &[u8]) -> Option<Vec<u8>> {
+        feed_from(reassembler, SOURCE, datagram)
+    }
+
+    fn feed_from(reassembler: &mut Reassembler, source: &str, datagram: &[u8]) -> Option<Vec<u8>> {
+        let packet = root::<ServerPacket>(datagram).unwrap();
+        reassembler.insert(source.parse().unwrap(), &packet.message_as_fragment().unwrap())
+    }
+
+    fn player_count(packet: &[u8]) -> usize {
+        let packet = root::<ServerPacket>(packet).unwrap();
+        packet.message_as_players_list().unwrap().players().unwrap().len()
+    }
+
+    #[test]
+    fn large_snapshot_round_trips() {
+        let packet = snapshot(500);
+        let datagrams = server_fragments(&packet, 7);
+        assert!(datagrams.len() > 1);
+        assert!(datagrams.iter().all(|d| d.len() + secure::OVERHEAD <= MAX_DATAGRAM_SIZE));
+
+        let mut reassembler = Reassembler::new();
+        let (last, rest) = datagrams.split_last().unwrap();
+        for datagram in rest {
+            assert!(feed(&mut reassembler, datagram).is_none());
+        }
+        let whole = feed(&mut reassembler, last).unwrap();
+        assert_eq!(whole, packet);
+        assert_eq!(player_count(&whole), 500);
+    }
+
+    #[test]
+    fn reordered_and_duplicated_fragments_reassemble() {
+        let packet = snapshot(300);
+        let mut datagrams = server_fragments(&packet, 1);
+        datagrams.reverse();
+        datagrams.insert(1, datagrams[0].clone());
+
+        let mut reassembler = Reassembler::new();
+        let whole = datagrams.iter().find_map(|d| feed(&mut reassembler, d)).unwrap();
+        assert_eq!(whole, packet);
+    }
+
+    #[test]
+    fn lost_fragment_drops_message() {
+        let packet = snapshot(300);
+        let mut datagrams = server_fragments(&packet, 2);
+        datagrams.remove(1);
+
+        let mut reassembler = Reassembler::new();
+        assert!(datagrams.iter().all(|d| feed(&mut reassembler, d).is_none()));
+
+        // The next message still gets through on its own.
+        let next = server_fragments(&packet, 3);
+        assert_eq!(next.iter().find_map(|d| feed(&mut reassembler, d)).unwrap(), packet);
+    }
+
+    #[test]
+    fn interleaved_messages_stay_separate() {
+        let first = snapshot(200);
+        let second = snapshot(250);
+        let a = server_fragments(&first, 10);
+        let b = server_fragments(&second, 11);
+
+        let mut reassembler = Reassembler::new();
+        let mut done = vec![];
+        for (x, y) in a.iter().zip(b.iter()) {
+            done.extend(feed(&mut reassembler, x));
+            done.extend(feed(&mut reassembler, y));
+        }
+        for d in a.iter().skip(b.len()).chain(b.iter().skip(a.len())) {
+            done.extend(feed(&mut reassembler, d));
+        }
+        assert_eq!(done.len(), 2);
+        assert!(done.contains(&first) && done.contains(&second));
+    }
+
+    #[test]
+    fn a_source_starting_many_messages_only_evicts_its_own() {
+        let packet = snapshot(300);
+        let mut reassembler = Reassembler::new();
+        let pending = server_fragments(&packet, 0);
+        let (last, rest) = pending.split_last().unwrap();
+        for datagram in rest {
+            assert!(feed_from(&mut reassembler, OTHER_SOURCE, datagram).is_none());
+        }
+
+        for message_id in 1..=MAX_PARTIAL_MESSAGES as u16 * 2 {
+            feed(&mut reassembler, &server_fragments(&packet, message_id)[0]);
+        }
+        let source: SocketAddr = SOURCE.parse().unwrap();
+        assert_eq!(reassembler.partial.keys().filter(|(addr, _)| *addr == source).count(), MAX_PARTIAL_MESSAGES);
+
+        // The other source's message was left alone and still completes.
+        assert_eq!(feed_from(&mut reassembler, OTHER_SOURCE, last).unwrap(), packet);
+    }
+
+    #[test]
+    fn oversized_packet_is_not_fragmented() {
+        let packet = vec![0u8; FRAGMENT_PAYLOAD * MAX_FRAGMENTS as usize + 1];
+        assert!(server_fragments(&packet, 0).is_empty());
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+
+use crate::schema_generated::PlayerCommand;
+
+// Depth is how many ticks ahead of the current one inputs are aimed, i.e. how much jitter we absorb.
+const MIN_DEPTH: u64 = 1;
+const MAX_DEPTH: u64 = 8;
+const INITIAL_DEPTH: u64 = 2;
+// Ticks without a late or missing input, and with a frame to spare, before the depth shrinks by one.
+const CALM_TICKS: u32 = 120;
+// Inputs aimed further ahead than this are from a confused or hostile client.
+const MAX_LEAD: u64 = 4 * MAX_DEPTH;
+
+pub const BUTTON_LEFT: u8 = 1 << 0;
+pub const BUTTON_RIGHT: u8 = 1 << 1;
+pub const BUTTON_JUMP: u8 = 1 << 2;
+pub const BUTTONS_ALL: u8 = BUTTON_LEFT | BUTTON_RIGHT | BUTTON_JUMP;
+
+/// What a PlayerInput says the client is holding, which stays held until the next state arrives.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Held {
+    /// `BUTTON_*` bits.
+    pub buttons: u8,
+    /// Stick deflection in [-1, 1].
+    pub move_x: f32,
+    /// Unit aim direction, or zero when not aiming.
+    pub aim_x: f32,
+    pub aim_y: f32,
+}
+
+impl Held {
+    /// Brings a client's raw state into range. The axes must be finite, which validation checks.
+    pub fn new(buttons: u8, move_x: f32, aim_x: f32, aim_y: f32) -> Held {
+        let length = (aim_x * aim_x + aim_y * aim_y).sqrt();
+        let (aim_x, aim_y) = if length > 1e-6 { (aim_x / length, aim_y / length) } else { (0.0, 0.0) };
+        Held {
+            buttons: buttons & BUTTONS_ALL,
+            move_x: move_x.clamp(-1.0, 1.0),
+            aim_x,
+            aim_y,
+        }
+    }
+
+    /// Horizontal movement in [-1, 1], from the stick and the left and right buttons together.
+    pub fn move_axis(&self) -> f32 {
+        let mut axis = self.move_x;
+        if self.buttons & BUTTON_RIGHT != 0 {
+            axis += 1.0;
+        }
+        if self.buttons & BUTTON_LEFT != 0 {
+            axis -= 1.0;
+        }
+        axis.clamp(-1.0, 1.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    /// Legacy presses from PlayerCommands, each applied once on the frame's tick.
+    Commands(Vec<PlayerCommand>),
+    Held(Held),
+}
+
+/// Everything one client asked for in one tick.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputFrame {
+    pub sequence: u32,
+    pub input: Input,
+}
+
+impl InputFrame {
+    /// Folds a later frame for the same tick into this one. Each command counts once per tick, so
+    /// a client sending faster than the tick rate doesn't move faster.
+    fn merge(&mut self, other: InputFrame) {
+        match (&mut self.input, other.input) {
+            (Input::Commands(commands), Input::Commands(others)) => {
+                for command in others {
+                    if !commands.contains(&command) {
+                        commands.push(command);
+                    }
+                }
+            }
+            // A button held at any point during the tick counts, so a quick tap isn't lost. The
+            // axes are whatever the client said last.
+            (Input::Held(held), Input::Held(other)) => {
+                *held = Held { buttons: held.buttons | other.buttons, ..other };
+            }
+            (input, other) => *input = other,
+        }
+        self.sequence = other.sequence;
+    }
+}
+
+/// One player's inputs, held until the tick they are meant for and handed out one frame per tick.
+///
+/// Clients stamp each frame with the server tick they want it applied on, aiming `depth()` ticks
+/// ahead. Frames without a stamp are queued behind whatever is already waiting. The depth grows
+/// when inputs arrive late or leave gaps, and shrinks again once the link has been calm for a while.
+pub struct InputBuffer {
+    frames: BTreeMap<u64, InputFrame>,
+    depth: u64,
+    // Whether frames have been coming out every tick since the buffer last ran empty.
+    streaming: bool,
+    // Set when the unstamped stream ran dry, to tell a stall from the player going idle.
+    dry_since: Option<u64>,
+    calm_ticks: u32,
+    min_spare: usize,
+    late: u64,
+    missing: u64,
+}
+
+impl InputBuffer {
+    pub fn new() -> InputBuffer {
+        InputBuffer {
+            frames: BTreeMap::new(),
+            depth: INITIAL_DEPTH,
+            streaming: false,
+            dry_since: None,
+            calm_ticks: 0,
+            min_spare: usize::MAX,
+            late: 0,
+            missing: 0,
+        }
+    }
+
+    /// How many ticks ahead clients should aim their inputs.
+    pub fn depth(&self) -> u64 {
+        self.depth
+    }
+
+    /// Frames that arrived after their tick had already run. They are applied on the next tick instead.
+    pub fn late(&self) -> u64 {
+        self.late
+    }
+
+    /// Ticks that went by without the frame that should have been there.
+    pub fn missing(&self) -> u64 {
+        self.missing
+    }
+
+    /// Buffers `frame` for `target_tick`, or for the next free tick if `target_tick` is 0.
+    /// `current_tick` is the tick about to be popped.
+    pub fn insert(&mut self, target_tick: u64, frame: InputFrame, current_tick: u64) {
+        let target = if target_tick == 0 {
+            let next_free = self.frames.last_key_value().map_or(0, |(tick, _)| tick + 1);
+            if self.frames.is_empty() && self.dry_since.is_some_and(|dry| current_tick <= dry + self.depth + 1) {
+                // The stream stalled rather than stopped: it should have still been flowing.
+                self.record_problem();
+                self.missing += 1;
+            }
+            self.dry_since = None;
+            (current_tick + self.depth).max(next_free)
+        } else if target_tick < current_tick {
+            self.record_problem();
+            self.late += 1;
+            current_tick
+        } else {
+            target_tick
+        };
+        if target > current_tick + MAX_LEAD {
+            return;
+        }
+        match self.frames.get_mut(&target) {
+            Some(existing) => existing.merge(frame),
+            None => {
+                self.frames.insert(target, frame);
+            }
+        }
+    }
+
+    /// The frame for `tick`, if any. Call once per tick, with increasing ticks.
+    pub fn pop(&mut self, tick: u64) -> Option<InputFrame> {
+        // Anything still filed under an earlier tick was never popped; it is late by now.
+        let mut frame: Option<InputFrame> = None;
+        while let Some(entry) = self.frames.first_entry() {
+            if *entry.key() > tick {
+                break;
+            }
+            let stale = *entry.key() < tick;
+            let next = entry.remove();
+            if stale {
+                self.late += 1;
+            }
+            match &mut frame {
+                Some(frame) => frame.merge(next),
+                None => frame = Some(next),
+            }
+        }
+
+        match &frame {
+            Some(_) => {
+                self.streaming = true;
+                self.dry_since = None;
+            }
+            // A hole in the middle of the stream. Before the first frame, waiting is expected.
+            None if self.streaming && !self.frames.is_empty() => {
+                self.record_problem();
+                self.missing += 1;
+            }
+            None if self.streaming => {
+                self.streaming = false;
+                self.dry_since = Some(tick);
+            }
+            None => {}
+        }
+
+        if frame.is_some() {
+            self.min_spare = self.min_spare.min(self.frames.len());
+            self.calm_ticks += 1;
+            if self.calm_ticks >= CALM_TICKS {
+                // Every frame had another one queued behind it, so one tick less of buffering would have done.
+                if self.min_spare >= 1 && self.depth > MIN_DEPTH {
+                    self.depth -= 1;
+                }
+                self.calm_ticks = 0;
+                self.min_spare = usize::MAX;
+            }
+        }
+        frame
+    }
+
+    fn record_problem(&mut self) {
+        self.depth = (self.depth + 1).min(MAX_DEPTH);
+        self.calm_ticks = 0;
+        self.min_spare = usize::MAX;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(sequence: u32, commands: &[PlayerCommand]) -> InputFrame {
+        InputFrame { sequence, input: Input::Commands(commands.to_vec()) }
+    }
+
+    fn held(sequence: u32, buttons: u8) -> InputFrame {
+        InputFrame { sequence, input: Input::Held(Held::new(buttons, 0.0, 0.0, 0.0)) }
+    }
+
+    #[test]
+    fn frames_come_out_on_their_tick_one_per_tick() {
+        let mut buffer = InputBuffer::new();
+        buffer.insert(12, frame(2, &[PlayerCommand::Jump]), 10);
+        buffer.insert(11, frame(1, &[PlayerCommand::Move_left]), 10);
+        assert_eq!(buffer.pop(10), None);
+        assert_eq!(buffer.pop(11), Some(frame(1, &[PlayerCommand::Move_left])));
+        assert_eq!(buffer.pop(12), Some(frame(2, &[PlayerCommand::Jump])));
+        assert_eq!(buffer.late(), 0);
+        assert_eq!(buffer.missing(), 0);
+    }
+
+    #[test]
+    fn frames_for_the_same_tick_merge_without_repeating_commands() {
+        let mut buffer = InputBuffer::new();
+        buffer.insert(5, frame(1, &[PlayerCommand::Move_right]), 3);
+        buffer.insert(5, frame(2, &[PlayerCommand::Move_right, PlayerCommand::Jump]), 3);
+        assert_eq!(buffer.pop(5), Some(frame(2, &[PlayerCommand::Move_right, PlayerCommand::Jump])));
+    }
+
+    #[test]
+    fn held_states_for_the_same_tick_keep_every_button() {
+        let mut buffer = InputBuffer::new();
+        buffer.insert(5, held(1, BUTTON_JUMP), 3);
+        buffer.insert(5, held(2, BUTTON_RIGHT), 3);
+        assert_eq!(buffer.pop(5), Some(held(2, BUTTON_JUMP | BUTTON_RIGHT)));
+
+        buffer.insert(6, frame(3, &[PlayerCommand::Jump]), 5);
+        buffer.insert(6, held(4, BUTTON_LEFT), 5);
+        assert_eq!(buffer.pop(6), Some(held(4, BUTTON_LEFT)));
+    }
+
+    #[test]
+    fn held_state_is_brought_into_range() {
+        let held = Held::new(0xff, 3.0, 3.0, -4.0);
+        assert_eq!(held.buttons, BUTTONS_ALL);
+        assert_eq!(held.move_x, 1.0);
+        assert_eq!((held.aim_x, held.aim_y), (0.6, -0.8));
+        assert_eq!(Held::new(0, -0.25, 0.0, 0.0).move_axis(), -0.25);
+        assert_eq!(Held::new(BUTTON_RIGHT, -0.25, 0.0, 0.0).move_axis(), 0.75);
+        assert_eq!(Held::new(BUTTON_RIGHT, 0.5, 0.0, 0.0).move_axis(), 1.0);
+        assert_eq!(Held::new(BUTTON_LEFT | BUTTON_RIGHT, 0.0, 0.0, 0.0).move_axis(), 0.0);
+    }
+
+    #[test]
+    fn late_frames_are_applied_next_tick_and_deepen_the_buffer() {
+        let mut buffer = InputBuffer::new();
+        let depth = buffer.depth();
+        buffer.insert(4, frame(1, &[PlayerCommand::Jump]), 6);
+        assert_eq!(buffer.late(), 1);
+        assert_eq!(buffer.depth(), depth + 1);
+        assert_eq!(buffer.pop(6), Some(frame(1, &[PlayerCommand::Jump])));
+    }
+
+    #[test]
+    fn gaps_count_as_missing() {
+        let mut buffer = InputBuffer::new();
+        buffer.insert(3, frame(1, &[]), 1);
+        buffer.insert(5, frame(3, &[]), 1);
+        assert!(buffer.pop(3).is_some());
+        assert!(buffer.pop(4).is_none());
+        assert_eq!(buffer.missing(), 1);
+    }
+
+    #[test]
+    fn unstamped_frames_queue_up_and_stalls_count_as_missing() {
+        let mut buffer = InputBuffer::new();
+        let depth = buffer.depth();
+        buffer.insert(0, frame(1, &[]), 10);
+        buffer.insert(0, frame(2, &[]), 10);
+        assert!(buffer.pop(10 + depth).is_some());
+        assert!(buffer.pop(11 + depth).is_some());
+        assert!(buffer.pop(12 + depth).is_none());
+        // The next frame shows up right after the stream ran dry, so it was held up, not idle.
+        buffer.insert(0, frame(3, &[]), 13 + depth);
+        assert_eq!(buffer.missing(), 1);
+        assert_eq!(buffer.depth(), depth + 1);
+
+        // A player who stops for a while isn't missing anything.
+        let mut idle = InputBuffer::new();
+        idle.insert(0, frame(1, &[]), 10);
+        for tick in 10..40 {
+            idle.pop(tick);
+        }
+        idle.insert(0, frame(2, &[]), 40);
+        assert_eq!(idle.missing(), 0);
+    }
+
+    #[test]
+    fn depth_shrinks_once_the_link_is_calm() {
+        let mut buffer = InputBuffer::new();
+        let depth = buffer.depth();
+        for tick in 0..CALM_TICKS as u64 + 10 {
+            buffer.insert(tick + depth, frame(tick as u32, &[]), tick);
+            buffer.pop(tick);
+        }
+        assert_eq!(buffer.depth(), depth - 1);
+    }
+
+    #[test]
+    fn far_future_frames_are_ignored() {
+        let mut buffer = InputBuffer::new();
+        buffer.insert(10 + MAX_LEAD + 1, frame(1, &[PlayerCommand::Jump]), 10);
+        assert!((10..=10 + MAX_LEAD + 1).all(|tick| buffer.pop(tick).is_none()));
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::collision::Body;
+
+/// A player's box and velocity at the end of one tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PastBody {
+    pub id: u32,
+    pub x: f32,
+    pub y: f32,
+    pub vel_x: f32,
+    pub vel_y: f32,
+    pub size: f32,
+}
+
+impl PastBody {
+    /// Whether this body overlaps the square at `x`, `y` with side `size`. Uses the live collision
+    /// check, so touching edges don't count.
+    pub fn overlaps(&self, x: f32, y: f32, size: f32) -> bool {
+        let area = Body { x, y, vel_x: 0.0, vel_y: 0.0, size, grounded: false };
+        let (overlap_x, overlap_y) = self.body().penetration(&area);
+        overlap_x > 0.0 && overlap_y > 0.0
+    }
+
+    /// The body as live collision sees it. Grounding isn't recorded, so it comes back false.
+    pub fn body(&self) -> Body {
+        Body {
+            x: self.x,
+            y: self.y,
+            vel_x: self.vel_x,
+            vel_y: self.vel_y,
+            size: self.size,
+            grounded: false,
+        }
+    }
+}
+
+/// The last few ticks of the world, so hits can be checked against what a lagging client saw
+/// rather than where everyone is now.
+pub struct WorldHistory {
+    ticks: VecDeque<(u64, Vec<PastBody>)>,
+    capacity: usize,
+    tick_duration: Duration,
+}
+
+impl WorldHistory {
+    /// Keeps enough ticks of `tick_duration` each to rewind by up to `max_rewind`.
+    pub fn new(max_rewind: Duration, tick_duration: Duration) -> WorldHistory {
+        let capacity = (max_rewind.as_micros() / tick_duration.as_micros().max(1)) as usize + 1;
+        WorldHistory {
+            ticks: VecDeque::with_capacity(capacity),
+            capacity,
+            tick_duration,
+        }
+    }
+
+    /// Stores the world as of `tick`, dropping the oldest tick once full. Ticks must be recorded in order.
+    pub fn record(&mut self, tick: u64, bodies: Vec<PastBody>) {
+        if self.ticks.back().is_some_and(|(newest, _)| *newest >= tick) {
+            return;
+        }
+        if self.ticks.len() == self.capacity {
+            self.ticks.pop_front();
+        }
+        self.ticks.push_back((tick, bodies));
+    }
+
+    pub fn oldest_tick(&self) -> Option<u64> {
+        self.ticks.front().map(|(tick, _)| *tick)
+    }
+
+    pub fn newest_tick(&self) -> Option<u64> {
+        self.ticks.back().map(|(tick, _)| *tick)
+    }
+
+    /// Every body as of `tick`, or None if that tick was never recorded or has been dropped.
+    pub fn at(&self, tick: u64) -> Option<&[PastBody]> {
+        let oldest = self.oldest_tick()?;
+        let (recorded, bodies) = self.ticks.get(tick.checked_sub(oldest)? as usize)?;
+        (*recorded == tick).then_some(&bodies[..])
+    }
+
+    /// Where player `id` was as of `tick`.
+    pub fn body_at(&self, tick: u64, id: u32) -> Option<PastBody> {
+        self.at(tick)?.iter().find(|body| body.id == id).copied()
+    }
+
+    /// The bodies that overlapped the square at `x`, `y` with side `size` as of `tick`.
+    pub fn overlapping(&self, tick: u64, x: f32, y: f32, size: f32) -> impl Iterator<Item = &PastBody> {
+        self.at(tick)
+            .unwrap_or_default()
+            .iter()
+            .filter(move |body| body.overlaps(x, y, size))
+    }
+
+    /// The tick a client was looking at `latency` ago, which for a shot is half its round trip
+    /// plus however far behind it renders. Clamped to the ticks still in the history, so a client
+    /// can't claim more lag than we are willing to rewind.
+    pub fn tick_seen(&self, latency: Duration) -> Option<u64> {
+        let newest = self.newest_tick()?;
+        let ticks_back = (latency.as_micros() / self.tick_duration.as_micros().max(1)) as u64;
+        Some(newest.saturating_sub(ticks_back).max(self.oldest_tick()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TICK: Duration = Duration::from_millis(10);
+
+    fn body(id: u32, x: f32) -> PastBody {
+        PastBody { id, x, y: 0.0, vel_x: 1.0, vel_y: 0.0, size: 10.0 }
+    }
+
+    fn history_of(ticks: u64) -> WorldHistory {
+        let mut history = WorldHistory::new(Duration::from_millis(50), TICK);
+        for tick in 1..=ticks {
+            history.record(tick, vec![body(1, tick as f32 * 5.0), body(2, 100.0)]);
+        }
+        history
+    }
+
+    #[test]
+    fn rewinds_to_recorded_ticks_only() {
+        let history = history_of(10);
+        assert_eq!(history.oldest_tick(), Some(5));
+        assert_eq!(history.body_at(7, 1).unwrap().x, 35.0);
+        assert_eq!(history.body_at(10, 1).unwrap().x, 50.0);
+        assert!(history.at(4).is_none());
+        assert!(history.at(11).is_none());
+        assert!(history.body_at(7, 3).is_none());
+    }
+
+    #[test]
+    fn collides_against_the_past_world() {
+        let history = history_of(10);
+        let hit = |tick| history.overlapping(tick, 30.0, 0.0, 2.0).map(|b| b.id).collect::<Vec<_>>();
+        // Player 1 covered x = 30 at tick 6 but has moved on by tick 10.
+        assert_eq!(hit(6), vec![1]);
+        assert!(hit(10).is_empty());
+        assert!(hit(1).is_empty());
+    }
+
+    #[test]
+    fn touching_is_not_a_hit() {
+        let past = body(1, 10.0);
+        assert!(past.overlaps(19.0, 0.0, 2.0));
+        assert!(!past.overlaps(20.0, 0.0, 2.0));
+        assert!(!past.overlaps(8.0, 0.0, 2.0));
+        assert!(!past.overlaps(10.0, 10.0, 2.0));
+    }
+
+    #[test]
+    fn latency_maps_to_a_clamped_tick() {
+        let history = history_of(10);
+        assert_eq!(history.tick_seen(Duration::ZERO), Some(10));
+        assert_eq!(history.tick_seen(Duration::from_millis(35)), Some(7));
+        assert_eq!(history.tick_seen(Duration::from_secs(3)), Some(5));
+        assert_eq!(history_of(0).tick_seen(Duration::ZERO), None);
+    }
+}
@@ -0,0 +1,399 @@
+use std::fmt;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::collision::Body;
+use crate::tilemap::TileMap;
+
+// Largest map side, in tiles, that a level may have.
+const MAX_MAP_TILES: usize = 4096;
+
+/// A level loaded from a map made in Tiled and exported as JSON, with the tile layer data left in
+/// the default CSV encoding.
+///
+/// - Tile layers named `collision`, or with a bool property `collision` set to true, are solid
+///   wherever they have a tile. All of them go into one `TileMap`; other tile layers are scenery.
+/// - Objects with the type (or, from Tiled 1.9 on, class) `spawn`, and every object in an object
+///   layer named `spawns`, are spawn points.
+/// - Every other object layer is a trigger layer, and its objects are the triggers.
+///
+/// Group layers are looked into. Layer names are matched ignoring case.
+#[derive(Clone, Debug)]
+pub struct Level {
+    pub name: String,
+    pub map: TileMap,
+    pub spawns: Vec<SpawnPoint>,
+    pub trigger_layers: Vec<TriggerLayer>,
+}
+
+/// Where a player appears: the middle of the bottom edge of their body goes here.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpawnPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TriggerLayer {
+    pub name: String,
+    pub triggers: Vec<Trigger>,
+}
+
+/// A rectangle in the level that something happens in. What happens is up to `kind` and the
+/// custom properties, which are kept as text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trigger {
+    pub name: String,
+    pub kind: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub properties: Vec<(String, String)>,
+}
+
+impl Trigger {
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Whether `body` is at least partly inside. Point objects have no area and never contain
+    /// anything.
+    pub fn overlaps(&self, body: &Body) -> bool {
+        body.x < self.x + self.width
+            && self.x < body.x + body.size
+            && body.y < self.y + self.height
+            && self.y < body.y + body.size
+    }
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    // The file is JSON but not a map we can use; says what's wrong with it.
+    Invalid(String),
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LevelError::Io(e) => write!(f, "can't read map: {}", e),
+            LevelError::Json(e) => write!(f, "map isn't a Tiled JSON map: {}", e),
+            LevelError::Invalid(reason) => write!(f, "unusable map: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
+
+fn invalid(reason: impl Into<String>) -> LevelError {
+    LevelError::Invalid(reason.into())
+}
+
+/// The parts of Tiled's JSON map format that levels are made from. Anything else in the file is
+/// ignored.
+#[derive(Deserialize)]
+struct TiledMap {
+    orientation: String,
+    #[serde(default)]
+    infinite: bool,
+    width: usize,
+    height: usize,
+    tilewidth: f32,
+    tileheight: f32,
+    layers: Vec<TiledLayer>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum TiledLayer {
+    TileLayer {
+        #[serde(default)]
+        name: String,
+        // Tile ids, or a string of them for layers saved in base64.
+        data: Option<Value>,
+        encoding: Option<String>,
+        #[serde(default)]
+        properties: Vec<TiledProperty>,
+    },
+    ObjectGroup {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        objects: Vec<TiledObject>,
+    },
+    Group {
+        #[serde(default)]
+        layers: Vec<TiledLayer>,
+    },
+    // Image layers, and whatever later versions of Tiled add.
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct TiledObject {
+    #[serde(default)]
+    name: String,
+    // Called the object's type, except in Tiled 1.9 where it was its class.
+    #[serde(default, rename = "type")]
+    kind: String,
+    #[serde(default)]
+    class: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    #[serde(default)]
+    properties: Vec<TiledProperty>,
+}
+
+#[derive(Deserialize)]
+struct TiledProperty {
+    name: String,
+    value: Value,
+}
+
+impl Level {
+    /// Loads a Tiled JSON export. The level is named after the file.
+    pub fn load(path: &Path) -> Result<Level, LevelError> {
+        let text = std::fs::read_to_string(path).map_err(LevelError::Io)?;
+        let name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        Level::parse(&name, &text)
+    }
+
+    pub fn parse(name: &str, text: &str) -> Result<Level, LevelError> {
+        let map: TiledMap = serde_json::from_str(text).map_err(LevelError::Json)?;
+        if map.orientation != "orthogonal" {
+            return Err(invalid("only orthogonal maps are supported"));
+        }
+        if map.infinite {
+            return Err(invalid("infinite maps aren't supported"));
+        }
+        if !(1..=MAX_MAP_TILES).contains(&map.width) || !(1..=MAX_MAP_TILES).contains(&map.height) {
+            return Err(invalid(format!("maps must be 1 to {} tiles on each side", MAX_MAP_TILES)));
+        }
+        if map.tileheight != map.tilewidth || map.tilewidth <= 0.0 {
+            return Err(invalid("tiles must be square"));
+        }
+
+        let mut level = Level {
+            name: name.to_string(),
+            map: TileMap::new(map.width, map.height, map.tilewidth),
+            spawns: vec![],
+            trigger_layers: vec![],
+        };
+        let mut collision_layers = 0;
+        level.add_layers(&map.layers, &mut collision_layers)?;
+        if collision_layers == 0 {
+            return Err(invalid("no collision layer"));
+        }
+        if level.spawns.is_empty() {
+            return Err(invalid("no spawn points"));
+        }
+        Ok(level)
+    }
+
+    fn add_layers(&mut self, layers: &[TiledLayer], collision_layers: &mut usize) -> Result<(), LevelError> {
+        for layer in layers {
+            match layer {
+                TiledLayer::TileLayer { name, data, encoding, properties } if is_collision_layer(name, properties) => {
+                    if encoding.as_ref().is_some_and(|encoding| encoding != "csv") {
+                        return Err(invalid(format!("layer '{}' must use CSV tile layer format", name)));
+                    }
+                    let data = data.as_ref().ok_or_else(|| invalid(format!("layer '{}' has no data", name)))?;
+                    let tiles = Vec::<u32>::deserialize(data).map_err(LevelError::Json)?;
+                    self.add_collision_layer(name, &tiles)?;
+                    *collision_layers += 1;
+                }
+                TiledLayer::ObjectGroup { name, objects } => self.add_object_layer(name, objects),
+                TiledLayer::Group { layers } => self.add_layers(layers, collision_layers)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn add_collision_layer(&mut self, name: &str, data: &[u32]) -> Result<(), LevelError> {
+        let (width, height) = (self.map.width(), self.map.height());
+        if data.len() != width * height {
+            return Err(invalid(format!("layer '{}' has {} tiles for a {}x{} map", name, data.len(), width, height)));
+        }
+        for (index, &tile) in data.iter().enumerate() {
+            // 0 is an empty cell; anything else is a tile, whichever tileset or flip it uses.
+            if tile != 0 {
+                self.map.set_solid(index % width, index / width, true);
+            }
+        }
+        Ok(())
+    }
+
+    fn add_object_layer(&mut self, name: &str, objects: &[TiledObject]) {
+        let spawn_layer = name.eq_ignore_ascii_case("spawns");
+        let mut triggers = vec![];
+        for object in objects {
+            let kind = if object.class.is_empty() { &object.kind } else { &object.class };
+            if spawn_layer || kind.eq_ignore_ascii_case("spawn") {
+                self.spawns.push(SpawnPoint { x: object.x, y: object.y });
+                continue;
+            }
+            triggers.push(Trigger {
+                name: object.name.clone(),
+                kind: kind.clone(),
+                x: object.x,
+                y: object.y,
+                width: object.width,
+                height: object.height,
+                properties: properties(&object.properties),
+            });
+        }
+        if !spawn_layer {
+            self.trigger_layers.push(TriggerLayer { name: name.to_string(), triggers });
+        }
+    }
+
+    /// The spawn point furthest from everyone already in the level, so new players don't land on
+    /// someone's head.
+    pub fn pick_spawn(&self, occupied: impl Iterator<Item = (f32, f32)> + Clone) -> SpawnPoint {
+        let clearance = |spawn: &SpawnPoint| {
+            occupied
+                .clone()
+                .map(|(x, y)| (x - spawn.x).powi(2) + (y - spawn.y).powi(2))
+                .fold(f32::INFINITY, f32::min)
+        };
+        // A level always has at least one spawn point; `parse` refuses maps without.
+        *self
+            .spawns
+            .iter()
+            .max_by(|a, b| clearance(a).total_cmp(&clearance(b)))
+            .unwrap()
+    }
+
+    /// Every trigger `body` is in, with the name of its layer.
+    pub fn triggers_at<'a>(&'a self, body: &'a Body) -> impl Iterator<Item = (&'a str, &'a Trigger)> + 'a {
+        self.trigger_layers.iter().flat_map(move |layer| {
+            layer
+                .triggers
+                .iter()
+                .filter(|trigger| trigger.overlaps(body))
+                .map(move |trigger| (layer.name.as_str(), trigger))
+        })
+    }
+}
+
+fn is_collision_layer(name: &str, properties: &[TiledProperty]) -> bool {
+    name.eq_ignore_ascii_case("collision")
+        || properties.iter().any(|p| p.name == "collision" && p.value == Value::Bool(true))
+}
+
+/// Custom properties as name and value, with the value written out as text whatever its type.
+fn properties(properties: &[TiledProperty]) -> Vec<(String, String)> {
+    properties
+        .iter()
+        .filter_map(|property| {
+            let value = match &property.value {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                _ => return None,
+            };
+            Some((property.name.clone(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Trimmed down from a real Tiled export: two tile layers, one of them collision by property,
+    // a spawn layer inside a group, and a trigger layer.
+    const MAP: &str = r#"{
+        "compressionlevel": -1, "height": 3, "infinite": false, "orientation": "orthogonal",
+        "renderorder": "right-down", "tiledversion": "1.10.2", "tileheight": 16, "tilewidth": 16,
+        "type": "map", "version": "1.10", "width": 4,
+        "layers": [
+            {"data": [0, 0, 0, 0, 5, 5, 0, 0, 0, 0, 0, 0], "height": 3, "id": 1, "name": "background",
+             "opacity": 1, "type": "tilelayer", "visible": true, "width": 4, "x": 0, "y": 0},
+            {"data": [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 2147483649], "height": 3, "id": 2, "name": "Ground",
+             "properties": [{"name": "collision", "type": "bool", "value": true}],
+             "type": "tilelayer", "visible": true, "width": 4, "x": 0, "y": 0},
+            {"id": 3, "layers": [
+                {"id": 4, "name": "Spawns", "type": "objectgroup", "objects": [
+                    {"id": 1, "name": "", "point": true, "type": "", "x": 8, "y": 32, "width": 0, "height": 0}
+                ]}
+            ], "name": "markers", "type": "group"},
+            {"id": 5, "name": "hazards", "type": "objectgroup", "objects": [
+                {"id": 2, "name": "pit", "class": "damage", "x": 40, "y": 20, "width": 24, "height": 12,
+                 "properties": [{"name": "amount", "type": "int", "value": 10},
+                                {"name": "message", "type": "string", "value": "ouch"}]},
+                {"id": 3, "name": "", "type": "spawn", "point": true, "x": 56, "y": 16}
+            ]}
+        ]
+    }"#;
+
+    #[test]
+    fn loads_collision_spawns_and_triggers() {
+        let level = Level::parse("test", MAP).unwrap();
+        assert_eq!((level.map.width(), level.map.height(), level.map.tile_size()), (4, 3, 16.0));
+        let map = &level.map;
+        let solid: Vec<bool> = (0..3).flat_map(|y| (0..4).map(move |x| map.is_solid(x, y))).collect();
+        // The scenery layer doesn't count, and a flipped tile is still a tile.
+        assert_eq!(solid, [false, false, false, false, false, false, false, false, true, true, true, true]);
+
+        assert_eq!(level.spawns, [SpawnPoint { x: 8.0, y: 32.0 }, SpawnPoint { x: 56.0, y: 16.0 }]);
+
+        assert_eq!(level.trigger_layers.len(), 1);
+        let pit = &level.trigger_layers[0].triggers[0];
+        assert_eq!(level.trigger_layers[0].triggers.len(), 1);
+        assert_eq!((pit.name.as_str(), pit.kind.as_str()), ("pit", "damage"));
+        assert_eq!(pit.property("amount"), Some("10"));
+        assert_eq!(pit.property("message"), Some("ouch"));
+    }
+
+    #[test]
+    fn finds_triggers_and_picks_the_emptiest_spawn() {
+        let level = Level::parse("test", MAP).unwrap();
+        let inside = Body { x: 50.0, y: 24.0, vel_x: 0.0, vel_y: 0.0, size: 8.0, grounded: false };
+        let names: Vec<(&str, &str)> = level.triggers_at(&inside).map(|(layer, t)| (layer, t.name.as_str())).collect();
+        assert_eq!(names, [("hazards", "pit")]);
+        let outside = Body { x: 0.0, ..inside };
+        assert_eq!(level.triggers_at(&outside).count(), 0);
+
+        assert_eq!(level.pick_spawn([(10.0, 30.0)].into_iter()), SpawnPoint { x: 56.0, y: 16.0 });
+        assert_eq!(level.pick_spawn([(50.0, 20.0)].into_iter()), SpawnPoint { x: 8.0, y: 32.0 });
+    }
+
+    #[test]
+    fn refuses_maps_it_cannot_use() {
+        let reason = |text: &str| match Level::parse("test", text) {
+            Err(LevelError::Invalid(reason)) => reason,
+            other => panic!("expected an invalid map, got {:?}", other),
+        };
+        assert_eq!(reason(&MAP.replace("\"tileheight\": 16", "\"tileheight\": 8")), "tiles must be square");
+        assert_eq!(reason(&MAP.replace("\"infinite\": false", "\"infinite\": true")), "infinite maps aren't supported");
+        assert_eq!(reason(&MAP.replace("\"value\": true", "\"value\": false")), "no collision layer");
+        let no_spawns = MAP.replace("Spawns", "doors").replace("\"spawn\"", "\"door\"");
+        assert_eq!(reason(&no_spawns), "no spawn points");
+        assert_eq!(reason(&MAP.replace("1, 1, 1, 2147483649", "1, 1, 1")), "layer 'Ground' has 11 tiles for a 4x3 map");
+        let base64 = MAP.replace("[0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 2147483649]", "\"AAAA\", \"encoding\": \"base64\"");
+        assert_eq!(reason(&base64), "layer 'Ground' must use CSV tile layer format");
+        assert!(matches!(Level::parse("test", "{\"layers\": ["), Err(LevelError::Json(_))));
+    }
+
+    #[test]
+    fn the_built_in_arena_loads() {
+        let level = Level::parse("arena", include_str!("../maps/arena.json")).unwrap();
+        assert_eq!((level.map.pixel_width(), level.map.pixel_height()), (640.0, 360.0));
+        // Spawn points stand on the floor or a platform, not inside them.
+        for spawn in &level.spawns {
+            let (x, y) = ((spawn.x / 20.0) as i64, (spawn.y / 20.0) as i64);
+            assert!(level.map.is_solid(x, y) && !level.map.is_solid(x, y - 1), "{:?}", spawn);
+        }
+    }
+}
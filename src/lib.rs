@@ -0,0 +1,9 @@
+//! The parts of the server that don't depend on the game loop's types. Clients written in Rust
+//! can reuse them as they are.
+
+pub mod broadphase;
+pub mod clock_sync;
+pub mod collision;
+pub mod lag_compensation;
+pub mod level;
+pub mod tilemap;
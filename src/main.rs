@@ -1,27 +1,99 @@
+use std::collections::VecDeque;
 use std::net::{SocketAddr, UdpSocket};
+use std::path::Path;
 use std::io::Result;
-use std::ops::Index;
-use std::os::unix::raw::time_t;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::thread;
 use std::thread::sleep;
 use std::time::{Duration, Instant};
-use flatbuffers::{root, FlatBufferBuilder};
+use flatbuffers::FlatBufferBuilder;
+use multi_server::clock_sync::{ClockSample, ClockSync};
+use multi_server::collision::{Body, Resolver};
+use multi_server::lag_compensation::{PastBody, WorldHistory};
+use multi_server::level::Level;
 
-#[allow(dead_code, unused_imports)]
+#[allow(dead_code, unused_imports, clippy::all, mismatched_lifetime_syntaxes)]
 #[path = "../schema_generated.rs"]
 mod schema_generated;
-pub use schema_generated::Player as SchemaPlayer;
-use crate::schema_generated::{PlayerCommand, PlayerCommands, Color, PlayerArgs};
+mod cookie;
+mod crypto;
+mod fragment;
+mod input_buffer;
+mod protocol;
+mod rate_limit;
+mod reliable;
+mod secure;
+mod sequence;
+mod session;
+mod snapshot;
+mod timestep;
+mod validation;
+use crate::schema_generated::{AdminAction, ClientMessage, ClientPacket, Connect, PlayerCommand, Color, ConnectStatus, LeaveReason, RejectReason, ServerMessage};
+use crate::cookie::CookieJar;
+use crate::fragment::Reassembler;
+use crate::input_buffer::{Held, Input, InputBuffer, InputFrame, BUTTON_JUMP};
+use crate::protocol::{finish_server_packet, PROTOCOL_VERSION};
+use crate::rate_limit::{RateLimiter, TokenBucket};
+use crate::reliable::{ReliableReceiver, ReliableSender};
+use crate::secure::{SecureConfig, SecureLink, SecureSession, Sealer};
+use crate::session::{PathProbe, Session, Sessions};
+use crate::snapshot::{EntityState, SnapshotHeader, SnapshotHistory};
+use crate::timestep::FixedStep;
+use crate::validation::{parse_client_packet, Offenders, Rejection};
 
+// Per room.
 const MAX_PLAYERS: usize = 10;
-const GRAVITY: f32 = 1.0;
-const FRICTION: f32 = 0.8;
+// Clients that arrive while their room is full wait here; 0 disables the queue.
+const JOIN_QUEUE_CAPACITY: usize = 10;
+// Simulation steps per second. Everything below is in seconds and pixels, so changing the rate
+// changes how finely the game is simulated but not how fast it plays.
+const TICK_RATE: u32 = 60;
+const TICK_DURATION: Duration = Duration::from_nanos(1_000_000_000 / TICK_RATE as u64);
+const DT: f32 = 1.0 / TICK_RATE as f32;
+// After this many steps in one go the tick thread gives up on catching up and skips ahead.
+const MAX_CATCH_UP_TICKS: u32 = 5;
+// Pixels per second squared.
+const GRAVITY: f32 = 3600.0;
+// Horizontal speed decays by e^(-FRICTION * seconds).
+const FRICTION: f32 = 13.4;
+// Seconds between jumps.
 const JUMP_CD: f32 = 0.3;
-const SCREEN_HEIGHT: usize = 360;
-const SCREEN_WIDTH: usize = 640;
-const TICK_DURATION: Duration = Duration::from_millis(16);
+// Played when no map is given with `--map`. It's a Tiled export like any other, so it can be opened
+// in Tiled as a starting point for new maps.
+const DEFAULT_MAP: &str = include_str!("../maps/arena.json");
+// Players inside a trigger of this kind are sent to their whole room, however far away they are.
+const SPOTLIGHT_TRIGGER: &str = "spotlight";
+// Where clients go when their Connect names no room. Other rooms are added with `--room`.
+const DEFAULT_ROOM: &str = "lobby";
+// Silence after which a player is dropped, unless changed with `--player-timeout`.
+const DEFAULT_PLAYER_TIMEOUT: Duration = Duration::from_secs(10);
+// How often the receive loop tells the tick thread a session is still sending. The player timeout
+// must be at least twice this, so a player who only pings or acks is never taken for gone.
+const HEARD_INTERVAL: Duration = Duration::from_secs(1);
+// How far back hit checks may rewind the world for a lagging client.
+const MAX_REWIND: Duration = Duration::from_millis(500);
+// Players further than this from a recipient are left out of its snapshots, unless changed with
+// `--interest-radius`.
+const DEFAULT_INTEREST_RADIUS: f32 = 480.0;
+// Input packets per second. Frames for the same tick are merged anyway, so this only needs to
+// cover a client sending a bit faster than the tick rate.
+const PLAYER_INPUTS_PER_SEC: f32 = 180.0;
+const PLAYER_INPUT_BURST: f32 = 6.0;
+// Chat is relayed to everyone reliably, so it gets a much smaller budget than movement.
+const PLAYER_CHAT_PER_SEC: f32 = 1.0;
+const PLAYER_CHAT_BURST: f32 = 3.0;
+// Hard cap on events waiting for the tick thread, in case it falls behind.
+const MAX_PENDING_EVENTS: usize = 4096;
+// A Connect must be at least this large, so a spoofed one can't be bounced back as a larger Challenge.
+const MIN_CONNECT_SIZE: usize = 128;
 const SERVER_ADDR: &str = "127.0.0.1:9000";
+// Hex-encoded 32-byte key. Setting it turns on secure mode and mixes the key into every session's keys.
+const PSK_ENV_VAR: &str = "MULTI_SERVER_PSK";
+// Admin commands are refused unless this is set and they carry the same token.
+const ADMIN_TOKEN_ENV_VAR: &str = "MULTI_SERVER_ADMIN_TOKEN";
+
+static NEXT_PLAYER_ID: AtomicU32 = AtomicU32::new(1);
 
 #[derive(Clone, Copy)]
 struct Vec2 {
@@ -33,220 +105,1465 @@ impl Vec2 {
     fn zero() -> Vec2 {
         Vec2 { x: 0.0, y: 0.0 }
     }
+
+    fn distance(&self, other: &Vec2) -> f32 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
 }
 
 struct Player {
+    id: u32,
+    // Index of the room the player is in. Players only see, touch and chat with their own room.
+    room: usize,
     ip: SocketAddr,
+    session_id: u64,
+    last_heard: Instant,
+    last_processed_input: u32,
+    inputs: InputBuffer,
+    // The newest PlayerInput, acted on every tick.
+    held: Held,
+    input_budget: TokenBucket,
+    dropped_inputs: u64,
+    chat_budget: TokenBucket,
+    // Fed by the client's pings; RTT, jitter and clock offset to this client.
+    clock: ClockSync,
+    snapshots: SnapshotHistory,
+    next_message_id: u16,
+    reliable: ReliableSender,
+    // Present when the player connected in secure mode; everything sent to them is sealed with it.
+    secure: Option<SecureLink>,
     pos: Vec2,
+    // Pixels per second.
     vel: Vec2,
+    // Pixels per second squared at full deflection.
     acc: f32,
+    // Upward speed a jump starts with, in pixels per second.
     jump_force: f32,
+    // Seconds since the last jump.
     jump_timer: f32,
+    // Standing on the ground or on another player as of the last tick.
+    grounded: bool,
     color: Color,
     size: f32,
+    // Sent to every client in the room regardless of distance. Set while standing in a spotlight trigger.
+    always_relevant: bool,
+}
+
+/// What decides who gets a player in their snapshots.
+#[derive(Clone, Copy)]
+struct Presence {
+    id: u32,
+    room: usize,
+    pos: Vec2,
+    always_relevant: bool,
+}
+
+impl Presence {
+    /// Whether `other` belongs in this player's snapshots: itself, and players in the same room
+    /// that are within `interest_radius` or always relevant.
+    fn sees(&self, other: &Presence, interest_radius: f32) -> bool {
+        self.room == other.room
+            && (other.always_relevant || other.id == self.id || self.pos.distance(&other.pos) <= interest_radius)
+    }
 }
 
 impl Player {
-    fn new(ip: SocketAddr) -> Player {
+    fn new(ip: SocketAddr, session_id: u64, secure: Option<SecureLink>, room: usize) -> Player {
         Player {
+            id: NEXT_PLAYER_ID.fetch_add(1, Ordering::Relaxed),
+            room,
             ip,
+            session_id,
+            last_heard: Instant::now(),
+            last_processed_input: 0,
+            inputs: InputBuffer::new(),
+            held: Held::default(),
+            input_budget: TokenBucket::new(PLAYER_INPUT_BURST, PLAYER_INPUTS_PER_SEC),
+            dropped_inputs: 0,
+            chat_budget: TokenBucket::new(PLAYER_CHAT_BURST, PLAYER_CHAT_PER_SEC),
+            clock: ClockSync::new(),
+            snapshots: SnapshotHistory::new(),
+            next_message_id: 0,
+            reliable: ReliableSender::new(),
+            secure,
             pos: Vec2::zero(),
             vel: Vec2::zero(),
-            acc: 0.75,
-            jump_force: 10.0,
+            acc: 2700.0,
+            jump_force: 600.0,
             jump_timer: 0.0,
+            grounded: false,
             color: Color::Red,
             size: 16.0,
+            always_relevant: false,
+        }
+    }
+
+    /// Forgets everything the client had acknowledged or sent, for a client that lost its state
+    /// and connected again without its token. Its character stays where it is.
+    fn start_over(&mut self) {
+        self.last_processed_input = 0;
+        self.inputs = InputBuffer::new();
+        self.held = Held::default();
+        self.snapshots = SnapshotHistory::new();
+        self.reliable = ReliableSender::new();
+    }
+
+    fn sealer(&self) -> Option<&Sealer> {
+        self.secure.as_ref().map(|link| link.sealer.as_ref())
+    }
+
+    fn network_stats(&self) -> String {
+        let latency = match self.clock.rtt() {
+            Some(rtt) => format!("rtt {:?}, jitter {:?}", rtt, self.clock.jitter()),
+            None => "rtt unknown".to_string(),
+        };
+        format!("{}, {} late and {} missing inputs", latency, self.inputs.late(), self.inputs.missing())
+    }
+
+    fn body(&self) -> Body {
+        Body {
+            x: self.pos.x,
+            y: self.pos.y,
+            vel_x: self.vel.x,
+            vel_y: self.vel.y,
+            size: self.size,
+            grounded: self.grounded,
+        }
+    }
+
+    fn set_body(&mut self, body: &Body) {
+        self.pos = Vec2 { x: body.x, y: body.y };
+        self.vel = Vec2 { x: body.vel_x, y: body.vel_y };
+        self.grounded = body.grounded;
+    }
+
+    fn past_body(&self) -> PastBody {
+        PastBody {
+            id: self.id,
+            x: self.pos.x,
+            y: self.pos.y,
+            vel_x: self.vel.x,
+            vel_y: self.vel.y,
+            size: self.size,
+        }
+    }
+
+    fn presence(&self) -> Presence {
+        Presence {
+            id: self.id,
+            room: self.room,
+            pos: self.pos,
+            always_relevant: self.always_relevant,
+        }
+    }
+
+    fn entity_state(&self) -> EntityState {
+        EntityState {
+            id: self.id,
+            x: self.pos.x,
+            y: self.pos.y,
+            color: self.color,
+            aim_x: self.held.aim_x,
+            aim_y: self.held.aim_y,
+        }
+    }
+}
+
+struct QueuedClient {
+    session_id: u64,
+    addr: SocketAddr,
+    last_heard: Instant,
+    secure: Option<SecureLink>,
+}
+
+/// A Connect that got past the cookie check.
+struct ConnectRequest {
+    addr: SocketAddr,
+    secure: Option<SecureLink>,
+    // The room the client asked for.
+    room: String,
+    // The client came without its token, so it has none of the session's earlier state.
+    fresh: bool,
+}
+
+/// Something the receive loop heard from a session, for the tick thread to act on.
+enum ClientEvent {
+    Connect(ConnectRequest),
+    // The session's packets now come from this address.
+    Migrate(SocketAddr),
+    Disconnect,
+    // Commands for one tick, and the tick they are for (0 for the next free one).
+    Input(u64, InputFrame),
+    Chat(String),
+    // Already checked against the admin token.
+    Admin(AdminAction, u32),
+    SnapshotAck(u64),
+    ReliableAck(u16),
+    // A completed ping exchange, with the server as the local end.
+    ClockSample(ClockSample),
+    // The session sent something that checked out, whatever it was.
+    Heard,
+}
+
+/// What the tick thread keeps for one of the games it runs, apart from the players.
+struct Room {
+    name: String,
+    level: Level,
+    join_queue: VecDeque<QueuedClient>,
+    history: WorldHistory,
+    collisions: Resolver,
+}
+
+impl Room {
+    fn new(name: String, level: Level) -> Room {
+        Room {
+            name,
+            level,
+            join_queue: VecDeque::new(),
+            history: WorldHistory::new(MAX_REWIND, TICK_DURATION),
+            collisions: Resolver::new(),
         }
     }
 }
 
+/// Settings that can be changed from the command line.
+#[derive(Clone, Copy)]
+struct Settings {
+    player_timeout: Duration,
+    // In pixels.
+    interest_radius: f32,
+}
+
+/// State kept by the receive loop.
+struct Ingest {
+    reassembler: Reassembler,
+    offenders: Offenders,
+    rate_limiter: RateLimiter,
+    cookies: CookieJar,
+    // Sessions whose clients came back with a valid cookie.
+    sessions: Sessions,
+    secure: Option<SecureConfig>,
+    admin_token: Option<String>,
+    player_timeout: Duration,
+    // Pongs and snapshots report time on this clock.
+    started: Instant,
+    // Sessions the tick thread has thrown out, to forget before the next datagram is handled.
+    ended_sessions: Arc<Mutex<Vec<u64>>>,
+}
+
 fn main() -> Result<()> {
+    let secure = secure_config()?;
+    let settings = settings()?;
+    let levels = load_rooms()?;
     let socket = Arc::new(UdpSocket::bind(SERVER_ADDR)?);
     println!("UDP running on {}...", SERVER_ADDR);
+    if let Some(config) = &secure {
+        println!("Secure mode on ({})", if config.psk.is_some() { "pre-shared key" } else { "anonymous key exchange" });
+    }
+    for (name, level) in &levels {
+        println!(
+            "Room '{}', level '{}': {}x{} tiles, {} spawn points, {} trigger layers",
+            name,
+            level.name,
+            level.map.width(),
+            level.map.height(),
+            level.spawns.len(),
+            level.trigger_layers.len()
+        );
+    }
     let players: Arc<Mutex<Vec<Player>>> = Arc::new(Mutex::new(Vec::new()));
-    let commands: Arc<Mutex<Vec<(SocketAddr, PlayerCommand)>>> = Arc::new(Mutex::new(Vec::new()));
+    let commands: Arc<Mutex<Vec<(u64, ClientEvent)>>> = Arc::new(Mutex::new(Vec::new()));
+    let ended_sessions: Arc<Mutex<Vec<u64>>> = Arc::new(Mutex::new(Vec::new()));
 
     let tick_players = Arc::clone(&players);
     let tick_commands = Arc::clone(&commands);
+    let tick_ended_sessions = Arc::clone(&ended_sessions);
     let tick_socket = Arc::clone(&socket);
+    let started = Instant::now();
 
     thread::spawn(move || {
+        let mut rooms: Vec<Room> = levels.into_iter().map(|(name, level)| Room::new(name, level)).collect();
+        let mut clock = FixedStep::new(TICK_DURATION, MAX_CATCH_UP_TICKS);
+        let mut tick_number: u64 = 0;
         loop {
-            let start = Instant::now();
-
-            let mut players_guard = tick_players.lock().unwrap();
-            let mut commands_guard = tick_commands.lock().unwrap();
-            tick(&mut players_guard, &mut commands_guard, &tick_socket);
-            drop(players_guard);
-            drop(commands_guard);
-
-            let sleep_time = TICK_DURATION.checked_sub(start.elapsed());
-            if let Some(sleep_time) = sleep_time {
-                sleep(sleep_time)
+            let steps = clock.advance(Instant::now());
+            if steps > 0 {
+                let start = Instant::now();
+                let mut players_guard = tick_players.lock().unwrap();
+                let mut commands_guard = tick_commands.lock().unwrap();
+                let mut ended_guard = tick_ended_sessions.lock().unwrap();
+                for _ in 0..steps {
+                    tick_number += 1;
+                    tick(&mut players_guard, &mut commands_guard, &mut rooms, &mut ended_guard, tick_number, &settings, &tick_socket);
+                }
+                // Catching up sends one snapshot of where things ended up, not one per step.
+                let server_time = started.elapsed().as_micros() as u64;
+                send_snapshots(&mut players_guard, tick_number, server_time, settings.interest_radius, &tick_socket);
+                drop(players_guard);
+                drop(commands_guard);
+                drop(ended_guard);
+                clock.record_work(start.elapsed());
             }
+            clock.report();
+            sleep(clock.until_next());
         }
     });
 
+    let mut ingest = Ingest {
+        reassembler: Reassembler::new(),
+        offenders: Offenders::new(),
+        rate_limiter: RateLimiter::new(),
+        cookies: CookieJar::new()?,
+        sessions: Sessions::new(),
+        secure,
+        admin_token: std::env::var(ADMIN_TOKEN_ENV_VAR).ok().filter(|token| !token.is_empty()),
+        player_timeout: settings.player_timeout,
+        started,
+        ended_sessions,
+    };
     loop {
         let mut buf = [0u8; 2048];
         let (amt, src_addr) = socket.recv_from(&mut buf)?;
+        ingest.rate_limiter.report();
+        for session_id in ingest.ended_sessions.lock().unwrap().drain(..) {
+            ingest.sessions.remove(session_id);
+        }
+        if ingest.offenders.is_banned(&src_addr.ip()) || !ingest.rate_limiter.allow(src_addr, Instant::now()) {
+            continue;
+        }
 
         let mut commands_guard = commands.lock().unwrap();
-        handle_packet(&buf[..amt], src_addr, &mut commands_guard);
+        if commands_guard.len() >= MAX_PENDING_EVENTS {
+            ingest.rate_limiter.record_overflow();
+            continue;
+        }
+        if let Err(rejection) = handle_datagram(&buf[..amt], src_addr, &mut commands_guard, &mut ingest, &socket) {
+            // Anyone can put someone else's address on a datagram, so only rejections from an
+            // address a session has verified count towards a ban. The rest are just dropped.
+            if ingest.sessions.id_for_addr(&src_addr).is_some() {
+                ingest.offenders.record(src_addr.ip(), &rejection);
+            }
+        }
         drop(commands_guard)
     }
 }
 
+/// Secure mode is turned on with `--secure`, or by providing a pre-shared key.
+fn secure_config() -> Result<Option<SecureConfig>> {
+    let psk = match std::env::var(PSK_ENV_VAR) {
+        Ok(hex) => Some(secure::parse_key(&hex).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{} must be 64 hex digits", PSK_ENV_VAR))
+        })?),
+        Err(_) => None,
+    };
+    let enabled = psk.is_some() || std::env::args().any(|arg| arg == "--secure");
+    Ok(enabled.then_some(SecureConfig { psk }))
+}
+
+/// `--player-timeout <seconds>` sets how long a silent player is kept, and `--interest-radius <pixels>`
+/// how far away other players show up in snapshots.
+fn settings() -> Result<Settings> {
+    let args: Vec<String> = std::env::args().collect();
+    let player_timeout = match flag_value(&args, "--player-timeout")? {
+        Some(seconds) => seconds
+            .parse::<f64>()
+            .ok()
+            .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+            .filter(|timeout| *timeout >= HEARD_INTERVAL * 2)
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("--player-timeout must be at least {} seconds", (HEARD_INTERVAL * 2).as_secs()),
+                )
+            })?,
+        None => DEFAULT_PLAYER_TIMEOUT,
+    };
+    let interest_radius = match flag_value(&args, "--interest-radius")? {
+        Some(pixels) => pixels
+            .parse::<f32>()
+            .ok()
+            .filter(|radius| radius.is_finite() && *radius > 0.0)
+            .ok_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, "--interest-radius must be a positive number of pixels")
+            })?,
+        None => DEFAULT_INTEREST_RADIUS,
+    };
+    Ok(Settings { player_timeout, interest_radius })
+}
+
+/// The argument following `flag`, if the flag was given at all.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Result<Option<&'a str>> {
+    match args.iter().position(|arg| arg == flag) {
+        Some(index) => match args.get(index + 1) {
+            Some(value) => Ok(Some(value)),
+            None => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{} needs a value", flag))),
+        },
+        None => Ok(None),
+    }
+}
+
+/// The default room plays the Tiled JSON file after `--map`, or the built-in arena. Every
+/// `--room <name>=<path>` adds a room with its own map, which clients pick by name when connecting.
+fn load_rooms() -> Result<Vec<(String, Level)>> {
+    let args: Vec<String> = std::env::args().collect();
+    let invalid = |message: String| std::io::Error::new(std::io::ErrorKind::InvalidInput, message);
+    let load = |path: &str| {
+        Level::load(Path::new(path)).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}: {}", path, e)))
+    };
+    let default = match flag_value(&args, "--map")? {
+        Some(path) => load(path)?,
+        None => Level::parse("arena", DEFAULT_MAP)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?,
+    };
+    let mut rooms = vec![(DEFAULT_ROOM.to_string(), default)];
+    for (index, _) in args.iter().enumerate().filter(|(_, arg)| *arg == "--room") {
+        let (name, path) = args
+            .get(index + 1)
+            .and_then(|spec| spec.split_once('='))
+            .filter(|(name, path)| !name.is_empty() && !path.is_empty())
+            .ok_or_else(|| invalid("--room needs <name>=<path of a Tiled JSON map>".into()))?;
+        if rooms.iter().any(|(existing, _)| existing == name) {
+            return Err(invalid(format!("room '{}' is given more than once", name)));
+        }
+        rooms.push((name.to_string(), load(path)?));
+    }
+    Ok(rooms)
+}
+
 fn tick(players: &mut MutexGuard<Vec<Player>>,
-        commands: &mut Vec<(SocketAddr, PlayerCommand)>,
+        commands: &mut Vec<(u64, ClientEvent)>,
+        rooms: &mut [Room],
+        ended_sessions: &mut Vec<u64>,
+        tick_number: u64,
+        settings: &Settings,
         socket: &UdpSocket) {
-    let mut prev_pos: Vec<(usize, Vec2)> = vec![];
-    for (index, p) in players.iter().enumerate() {
-        prev_pos.push((index, p.pos))
-    }
-    for (addr, cmd) in commands.iter() {
-        if let Some(player) = get_player_by_ip(addr, players) {
-            match cmd {
-                &PlayerCommand::Move_right => handle_move_right(player),
-                &PlayerCommand::Move_left => handle_move_left(player),
-                &PlayerCommand::Jump => handle_jump(player),
-                _ => {}
+    for (session_id, event) in commands.iter() {
+        match event {
+            ClientEvent::Connect(request) => handle_connect(*session_id, request, players, rooms, ended_sessions, socket),
+            ClientEvent::Migrate(addr) => migrate_session(*session_id, addr, players, rooms),
+            ClientEvent::Disconnect => {
+                for room in rooms.iter_mut() {
+                    room.join_queue.retain(|q| q.session_id != *session_id);
+                }
+                handle_disconnect(*session_id, players)
+            }
+            ClientEvent::Input(target_tick, frame) => {
+                if let Some(player) = get_player_by_session(*session_id, players) {
+                    if !player.input_budget.try_take(Instant::now()) {
+                        player.dropped_inputs += 1;
+                        continue;
+                    }
+                    player.inputs.insert(*target_tick, frame.clone(), tick_number);
+                }
+            }
+            ClientEvent::Chat(text) => {
+                if let Some(player) = get_player_by_session(*session_id, players) {
+                    if !player.chat_budget.try_take(Instant::now()) {
+                        continue;
+                    }
+                    let (player_id, room) = (player.id, player.room);
+                    broadcast_chat(players, room, player_id, text);
+                }
+            }
+            ClientEvent::Admin(action, player_id) => {
+                if *action == AdminAction::Kick {
+                    kick_player(*player_id, players, ended_sessions, socket);
+                }
+            }
+            ClientEvent::SnapshotAck(tick) => {
+                if let Some(player) = get_player_by_session(*session_id, players) {
+                    player.snapshots.ack(*tick);
+                }
+            }
+            ClientEvent::ReliableAck(ack) => {
+                if let Some(player) = get_player_by_session(*session_id, players) {
+                    player.reliable.ack(*ack);
+                }
+            }
+            ClientEvent::ClockSample(sample) => {
+                if let Some(player) = get_player_by_session(*session_id, players) {
+                    player.clock.observe(*sample);
+                }
+            }
+            ClientEvent::Heard => {
+                let now = Instant::now();
+                if let Some(player) = get_player_by_session(*session_id, players) {
+                    player.last_heard = now;
+                }
+                for queued in rooms.iter_mut().flat_map(|room| room.join_queue.iter_mut()) {
+                    if queued.session_id == *session_id {
+                        queued.last_heard = now;
+                    }
+                }
             }
-        } else {
-            println!("New player connected: {}", addr);
-            players.push(Player::new(*addr));
         }
     }
+    evict_idle_players(players, ended_sessions, settings.player_timeout, socket);
+    admit_queued_clients(players, rooms, settings.player_timeout, socket);
+    apply_inputs(players, tick_number);
 
-    physics(players);
-    let player_forces = collision(players);
-    for (i, force, pos) in player_forces {
-        players[i].vel.x = force.x;
-        players[i].vel.y = force.y;
-        players[i].pos.x = pos.x;
-        players[i].pos.y = pos.y;
+    physics(players, rooms);
+    for (index, room) in rooms.iter_mut().enumerate() {
+        let in_room: Vec<usize> = (0..players.len()).filter(|&i| players[i].room == index).collect();
+        // Players shoved by other players are kept out of the level's walls and floors.
+        let mut bodies: Vec<Body> = in_room.iter().map(|&i| players[i].body()).collect();
+        let map = &room.level.map;
+        room.collisions.resolve(&mut bodies, |body| map.push_out(body));
+        for (&i, body) in in_room.iter().zip(&bodies) {
+            players[i].set_body(body);
+            players[i].always_relevant = room.level.triggers_at(body).any(|(_, trigger)| trigger.kind == SPOTLIGHT_TRIGGER);
+        }
+        room.history.record(tick_number, in_room.iter().map(|&i| players[i].past_body()).collect());
     }
 
+    commands.clear();
+}
 
+fn send_snapshots(players: &mut [Player], tick_number: u64, server_time: u64, interest_radius: f32, socket: &UdpSocket) {
+    // Each recipient has its own acknowledgements, delta baseline, room and area of interest,
+    // so snapshots are built per player.
+    let entities: Vec<(EntityState, Presence)> = players.iter().map(|p| (p.entity_state(), p.presence())).collect();
     let mut builder = FlatBufferBuilder::with_capacity(2048);
-    let players_offsets: Vec<_> = players
-        .iter()
-        .map(|p| {
-            let args = PlayerArgs {
-                x: p.pos.x,
-                y: p.pos.y,
-                color: p.color,
-            };
-            SchemaPlayer::create(&mut builder, &args)
-        })
-        .collect();
+    for recipient in players.iter_mut() {
+        let viewer = recipient.presence();
+        let visible: Vec<EntityState> = entities
+            .iter()
+            .filter(|(_, presence)| viewer.sees(presence, interest_radius))
+            .map(|(entity, _)| *entity)
+            .collect();
+
+        builder.reset();
+        let header = SnapshotHeader {
+            tick: tick_number,
+            server_time,
+            input_lead: recipient.inputs.depth() as u8,
+            last_processed_input: recipient.last_processed_input,
+            your_id: recipient.id,
+        };
+        let (message_type, message) = recipient.snapshots.write(&mut builder, &header, &visible);
+        finish_server_packet(&mut builder, message_type, message);
+        let _ = fragment::send_to(socket, &recipient.ip, builder.finished_data(), recipient.next_message_id, recipient.sealer());
+        recipient.next_message_id = recipient.next_message_id.wrapping_add(1);
+
+        for packet in recipient.reliable.due_packets(&mut builder) {
+            let _ = fragment::send_to(socket, &recipient.ip, &packet, recipient.next_message_id, recipient.sealer());
+            recipient.next_message_id = recipient.next_message_id.wrapping_add(1);
+        }
+    }
+}
+
+fn apply_inputs(players: &mut [Player], tick_number: u64) {
+    for player in players {
+        if let Some(frame) = player.inputs.pop(tick_number) {
+            player.last_processed_input = frame.sequence;
+            match frame.input {
+                Input::Commands(commands) => {
+                    for command in commands {
+                        match command {
+                            PlayerCommand::Move_right => handle_move_right(player),
+                            PlayerCommand::Move_left => handle_move_left(player),
+                            PlayerCommand::Jump => handle_jump(player),
+                            _ => {}
+                        }
+                    }
+                }
+                Input::Held(held) => player.held = held,
+            }
+        }
+
+        // Held input acts once per tick, also on ticks where no new state arrived.
+        let axis = player.held.move_axis();
+        if axis != 0.0 {
+            handle_move(player, axis);
+        }
+        if player.held.buttons & BUTTON_JUMP != 0 {
+            handle_jump(player);
+        }
+    }
+}
+
+fn handle_connect(session_id: u64,
+                  request: &ConnectRequest,
+                  players: &mut MutexGuard<Vec<Player>>,
+                  rooms: &mut [Room],
+                  ended_sessions: &mut Vec<u64>,
+                  socket: &UdpSocket) {
+    let ConnectRequest { addr, room: room_name, .. } = request;
+    let secure = request.secure.clone();
+    // A repeated Connect for a known session means our Accept got lost or the client moved, so
+    // point the player at where the client is now and resend it.
+    if let Some(player) = get_player_by_session(session_id, players) {
+        if player.ip != *addr {
+            println!("Player {} moved from {} to {} (session {})", player.id, player.ip, addr, session_id);
+            player.ip = *addr;
+        }
+        player.last_heard = Instant::now();
+        if request.fresh {
+            player.start_over();
+        }
+        // The client may have started over with a new key exchange.
+        player.secure = secure;
+        let public_key = player.secure.as_ref().map(|link| &link.public_key[..]);
+        send_connect_response(socket, addr, ConnectStatus::Accepted, player.session_id, RejectReason::None, 0, public_key);
+        return;
+    }
+    let Some(room_index) = rooms.iter().position(|room| room.name == *room_name) else {
+        for room in rooms.iter_mut() {
+            room.join_queue.retain(|q| q.session_id != session_id);
+        }
+        reject(session_id, addr, RejectReason::UnknownRoom, ended_sessions, socket);
+        return;
+    };
+    // A queued client that changed its mind gives up its place in the other room's queue.
+    for (index, room) in rooms.iter_mut().enumerate() {
+        if index != room_index {
+            room.join_queue.retain(|q| q.session_id != session_id);
+        }
+    }
+    let Room { level, join_queue, .. } = &mut rooms[room_index];
+    if room_population(players, room_index) < MAX_PLAYERS && join_queue.is_empty() {
+        admit_player(session_id, addr, secure, room_index, players, level, socket);
+        return;
+    }
+
+    // Queued clients keep resending Connect, which refreshes their spot and tells them where they are.
+    let position = match join_queue.iter().position(|q| q.session_id == session_id) {
+        Some(position) => {
+            join_queue[position].addr = *addr;
+            join_queue[position].last_heard = Instant::now();
+            join_queue[position].secure = secure;
+            position
+        }
+        None if join_queue.len() < JOIN_QUEUE_CAPACITY => {
+            join_queue.push_back(QueuedClient { session_id, addr: *addr, last_heard: Instant::now(), secure });
+            join_queue.len() - 1
+        }
+        None => {
+            reject(session_id, addr, RejectReason::ServerFull, ended_sessions, socket);
+            return;
+        }
+    };
+    send_connect_response(socket, addr, ConnectStatus::Queued, session_id, RejectReason::ServerFull, position as u16 + 1, None);
+}
+
+/// Turns a client away without a player. Its session goes too, so the client has to go through
+/// the cookie handshake again to retry, as after a kick.
+fn reject(session_id: u64, addr: &SocketAddr, reason: RejectReason, ended_sessions: &mut Vec<u64>, socket: &UdpSocket) {
+    send_connect_response(socket, addr, ConnectStatus::Rejected, 0, reason, 0, None);
+    ended_sessions.push(session_id);
+}
+
+fn admit_player(session_id: u64,
+                addr: &SocketAddr,
+                secure: Option<SecureLink>,
+                room: usize,
+                players: &mut MutexGuard<Vec<Player>>,
+                level: &Level,
+                socket: &UdpSocket) {
+    println!("New player connected: {} (session {}, room {})", addr, session_id, room);
+    let public_key = secure.as_ref().map(|link| link.public_key);
+    let mut player = Player::new(*addr, session_id, secure, room);
+    let occupied = players.iter().filter(|p| p.room == room).map(|p| (p.pos.x + p.size / 2.0, p.pos.y + p.size));
+    let spawn = level.pick_spawn(occupied);
+    player.pos = Vec2 { x: spawn.x - player.size / 2.0, y: spawn.y - player.size };
+    players.push(player);
+    send_connect_response(socket, addr, ConnectStatus::Accepted, session_id, RejectReason::None, 0, public_key.as_ref().map(|k| &k[..]));
+}
+
+fn admit_queued_clients(players: &mut MutexGuard<Vec<Player>>, rooms: &mut [Room], timeout: Duration, socket: &UdpSocket) {
+    for (index, room) in rooms.iter_mut().enumerate() {
+        room.join_queue.retain(|q| q.last_heard.elapsed() <= timeout);
+        while room_population(players, index) < MAX_PLAYERS {
+            match room.join_queue.pop_front() {
+                Some(queued) => admit_player(queued.session_id, &queued.addr, queued.secure, index, players, &room.level, socket),
+                None => break,
+            }
+        }
+    }
+}
+
+fn room_population(players: &[Player], room: usize) -> usize {
+    players.iter().filter(|p| p.room == room).count()
+}
+
+fn migrate_session(session_id: u64,
+                   addr: &SocketAddr,
+                   players: &mut MutexGuard<Vec<Player>>,
+                   rooms: &mut [Room]) {
+    if let Some(player) = get_player_by_session(session_id, players) {
+        println!("Player {} moved from {} to {} (session {})", player.id, player.ip, addr, session_id);
+        player.ip = *addr;
+    }
+    for room in rooms.iter_mut() {
+        if let Some(queued) = room.join_queue.iter_mut().find(|q| q.session_id == session_id) {
+            queued.addr = *addr;
+        }
+    }
+}
+
+fn handle_disconnect(session_id: u64, players: &mut MutexGuard<Vec<Player>>) {
+    if let Some(index) = players.iter().position(|p| p.session_id == session_id) {
+        let player = players.remove(index);
+        println!("Player disconnected: {} (session {}, {} inputs dropped, {})", player.ip, session_id, player.dropped_inputs, player.network_stats());
+        broadcast_player_left(players, player.room, player.id, LeaveReason::Disconnected);
+    }
+}
+
+fn evict_idle_players(players: &mut MutexGuard<Vec<Player>>, ended_sessions: &mut Vec<u64>, timeout: Duration, socket: &UdpSocket) {
+    let now = Instant::now();
+    let mut evicted = vec![];
+    players.retain(|p| {
+        // A client that keeps sending but never acks reliable messages is as good as gone too.
+        let idle = now.duration_since(p.last_heard) > timeout || p.reliable.stalled(now);
+        if idle {
+            println!("Player timed out: {} (session {}, {} inputs dropped, {})", p.ip, p.session_id, p.dropped_inputs, p.network_stats());
+            // In case the client is still there but can't get through, it is told it's out and
+            // has to connect again.
+            let _ = secure::send_to(socket, &p.ip, &player_left_packet(p.id, LeaveReason::TimedOut), p.sealer());
+            ended_sessions.push(p.session_id);
+            evicted.push((p.room, p.id));
+        }
+        !idle
+    });
+    for (room, player_id) in evicted {
+        broadcast_player_left(players, room, player_id, LeaveReason::TimedOut);
+    }
+}
+
+fn kick_player(player_id: u32, players: &mut MutexGuard<Vec<Player>>, ended_sessions: &mut Vec<u64>, socket: &UdpSocket) {
+    if let Some(index) = players.iter().position(|p| p.id == player_id) {
+        let player = players.remove(index);
+        println!("Player kicked: {} (session {})", player.ip, player.session_id);
+        // Sent once and unreliably, since the player won't be around to have it resent.
+        let _ = secure::send_to(socket, &player.ip, &player_left_packet(player_id, LeaveReason::Kicked), player.sealer());
+        // Without a session the client has to go through the cookie handshake again to rejoin.
+        ended_sessions.push(player.session_id);
+        broadcast_player_left(players, player.room, player_id, LeaveReason::Kicked);
+    }
+}
 
-    let players_vec = builder.create_vector(&players_offsets);
-    let players_list = schema_generated::PlayersList::create(
+fn broadcast_chat(players: &mut [Player], room: usize, player_id: u32, text: &str) {
+    let mut builder = FlatBufferBuilder::with_capacity(64 + text.len());
+    let text = builder.create_string(text);
+    let chat = schema_generated::Chat::create(
         &mut builder,
-        &schema_generated::PlayersListArgs {
-            players: Some(players_vec),
-        },
+        &schema_generated::ChatArgs { player_id, text: Some(text) },
     );
-    builder.finish(players_list, None);
+    finish_server_packet(&mut builder, ServerMessage::Chat, chat.as_union_value());
     let bytes = builder.finished_data();
-    for p in players.iter() {
-        let _ = socket.send_to(bytes, p.ip);
+    for p in players.iter_mut().filter(|p| p.room == room) {
+        p.reliable.send(bytes.to_vec());
     }
+}
 
-    commands.clear();
+fn player_left_packet(player_id: u32, reason: LeaveReason) -> Vec<u8> {
+    let mut builder = FlatBufferBuilder::with_capacity(64);
+    let player_left = schema_generated::PlayerLeft::create(
+        &mut builder,
+        &schema_generated::PlayerLeftArgs { player_id, reason },
+    );
+    finish_server_packet(&mut builder, ServerMessage::PlayerLeft, player_left.as_union_value());
+    builder.finished_data().to_vec()
 }
 
+fn broadcast_player_left(players: &mut [Player], room: usize, player_id: u32, reason: LeaveReason) {
+    let bytes = player_left_packet(player_id, reason);
+    for p in players.iter_mut().filter(|p| p.room == room) {
+        p.reliable.send(bytes.clone());
+    }
+}
 
-fn handle_packet(packet: &[u8], src_addr: SocketAddr, commands: &mut MutexGuard<Vec<(SocketAddr, PlayerCommand)>>) {
-    let player_commands = root::<PlayerCommands>(packet).expect("No command received");
-    if let Some(cmd_list) = player_commands.commands() {
-        for cmd in cmd_list {
-            commands.push((src_addr, cmd));
+fn send_connect_response(socket: &UdpSocket,
+                         addr: &SocketAddr,
+                         status: ConnectStatus,
+                         session_id: u64,
+                         reason: RejectReason,
+                         queue_position: u16,
+                         public_key: Option<&[u8]>) {
+    let mut builder = FlatBufferBuilder::with_capacity(128);
+    let public_key = public_key.map(|key| builder.create_vector(key));
+    let response = schema_generated::ConnectResponse::create(
+        &mut builder,
+        &schema_generated::ConnectResponseArgs { status, session_id, reason, queue_position, public_key },
+    );
+    finish_server_packet(&mut builder, ServerMessage::ConnectResponse, response.as_union_value());
+    let _ = socket.send_to(builder.finished_data(), addr);
+}
+
+fn send_challenge(socket: &UdpSocket, addr: &SocketAddr, cookie: &[u8]) {
+    let mut builder = FlatBufferBuilder::with_capacity(MIN_CONNECT_SIZE);
+    let cookie = builder.create_vector(cookie);
+    let challenge = schema_generated::Challenge::create(
+        &mut builder,
+        &schema_generated::ChallengeArgs { cookie: Some(cookie) },
+    );
+    finish_server_packet(&mut builder, ServerMessage::Challenge, challenge.as_union_value());
+    debug_assert!(builder.finished_data().len() <= MIN_CONNECT_SIZE);
+    let _ = socket.send_to(builder.finished_data(), addr);
+}
+
+/// Tokens come from the OS random number generator, since knowing one is enough to take over
+/// the session when not in secure mode.
+fn new_session_id() -> Result<u64> {
+    let mut bytes = [0u8; 8];
+    crypto::random_bytes(&mut bytes)?;
+    // Zero is the schema default, so keep it free to mean "no session".
+    Ok(u64::from_le_bytes(bytes).max(1))
+}
+
+/// Opens sealed datagrams. In secure mode nothing else gets past here except the handshake.
+fn handle_datagram(datagram: &[u8],
+                   src_addr: SocketAddr,
+                   commands: &mut MutexGuard<Vec<(u64, ClientEvent)>>,
+                   ingest: &mut Ingest,
+                   socket: &UdpSocket) -> std::result::Result<(), Rejection> {
+    if let Some(session_id) = ingest.sessions.id_for_datagram(datagram) {
+        let opened = ingest.sessions.get_mut(session_id)
+            .and_then(|session| session.secure.as_mut())
+            .and_then(|secure| secure.opener.open(datagram));
+        if let Some(packet) = opened {
+            let moved = ingest.sessions.get(session_id).is_some_and(|session| session.addr != src_addr);
+            if moved && !path_confirmed(session_id, &packet, src_addr, ingest, socket) {
+                return Ok(());
+            }
+            if ingest.sessions.migrate(session_id, src_addr).is_some() {
+                commands.push((session_id, ClientEvent::Migrate(src_addr)));
+            }
+            return handle_packet(&packet, src_addr, Some(session_id), commands, ingest, socket);
+        }
+    }
+    if ingest.secure.is_none() {
+        return handle_packet(datagram, src_addr, None, commands, ingest, socket);
+    }
+
+    // Anything else failed authentication, which says nothing about who really sent it, so it is
+    // dropped without counting against the source address.
+    match parse_client_packet(datagram) {
+        Ok(client_packet) => {
+            if let Some(connect) = client_packet.message_as_connect() {
+                handle_connect_request(connect, datagram.len(), client_packet.session_id(), src_addr, commands, ingest, socket);
+            }
+        }
+        Err(Rejection::VersionMismatch(version, message_type)) => {
+            reject_version(version, message_type, datagram.len(), src_addr, socket)
         }
+        Err(_) => {}
+    }
+    Ok(())
+}
+
+/// Whether a sealed packet from an address other than its session's answers a PathChallenge
+/// sent there. Only the session's owner can seal for it, but anyone on the path can replay what
+/// it sealed from a spoofed address, so the session only moves once the new address has shown it
+/// can read what is sent there. Anything else from the new address is dropped, and prompts a
+/// PathChallenge if one is due.
+fn path_confirmed(session_id: u64, packet: &[u8], src_addr: SocketAddr, ingest: &mut Ingest, socket: &UdpSocket) -> bool {
+    let Some(session) = ingest.sessions.get_mut(session_id) else {
+        return false;
+    };
+    let response = parse_client_packet(packet).ok().and_then(|p| p.message_as_path_response()).map(|r| r.nonce());
+    if response.is_some_and(|nonce| session.answers_probe(&src_addr, nonce)) {
+        return true;
+    }
+    let now = Instant::now();
+    let Some(secure) = session.secure.as_ref().filter(|_| session.may_probe(now)) else {
+        return false;
+    };
+    let mut nonce = [0u8; 8];
+    if let Err(e) = crypto::random_bytes(&mut nonce) {
+        println!("Can't generate a path challenge: {}", e);
+        return false;
+    }
+    let nonce = u64::from_le_bytes(nonce);
+    let mut builder = FlatBufferBuilder::with_capacity(64);
+    let challenge = schema_generated::PathChallenge::create(&mut builder, &schema_generated::PathChallengeArgs { nonce });
+    finish_server_packet(&mut builder, ServerMessage::PathChallenge, challenge.as_union_value());
+    let _ = secure::send_to(socket, &src_addr, builder.finished_data(), Some(secure.link.sealer.as_ref()));
+    session.probe = Some(PathProbe { addr: src_addr, nonce, sent: now });
+    false
+}
+
+/// Tells a client speaking another protocol version why it can't join. Other packets from it are
+/// dropped quietly, since an outdated client isn't an attacker.
+fn reject_version(version: u16, message_type: ClientMessage, packet_len: usize, src_addr: SocketAddr, socket: &UdpSocket) {
+    if message_type != ClientMessage::Connect || packet_len < MIN_CONNECT_SIZE {
+        return;
     }
+    println!("Rejected {}: protocol version {}, expected {}", src_addr, version, PROTOCOL_VERSION);
+    send_connect_response(socket, &src_addr, ConnectStatus::Rejected, 0, RejectReason::VersionMismatch, 0, None);
 }
 
-fn collision(players: &[Player]) -> Vec<(usize, Vec2, Vec2)> {
-    let mut player_forces = vec![];
-    for (i, p1) in players.iter().enumerate() {
-        for p2 in players {
-            if p1.ip == p2.ip {
-                continue;
+/// `sealed_session` is the session a sealed packet was opened with; plain packets name theirs.
+fn handle_packet(packet: &[u8],
+                 src_addr: SocketAddr,
+                 sealed_session: Option<u64>,
+                 commands: &mut MutexGuard<Vec<(u64, ClientEvent)>>,
+                 ingest: &mut Ingest,
+                 socket: &UdpSocket) -> std::result::Result<(), Rejection> {
+    let client_packet = match parse_client_packet(packet) {
+        Err(Rejection::VersionMismatch(version, message_type)) => {
+            reject_version(version, message_type, packet.len(), src_addr, socket);
+            return Ok(());
+        }
+        parsed => parsed?,
+    };
+    let session_id = sealed_session.unwrap_or(client_packet.session_id());
+    if let Some(connect) = client_packet.message_as_connect() {
+        handle_connect_request(connect, packet.len(), session_id, src_addr, commands, ingest, socket);
+        return Ok(());
+    }
+    // Everything else must come from where its session was last verified to be. Moving a plain
+    // session takes a new Connect, whose cookie proves the new address.
+    match ingest.sessions.get_mut(session_id) {
+        Some(session) if session.addr == src_addr => {
+            let now = Instant::now();
+            session.last_heard = now;
+            // Players are timed out on this too, so the tick thread hears about it now and then.
+            if session.last_reported.is_none_or(|reported| now.duration_since(reported) >= HEARD_INTERVAL) {
+                session.last_reported = Some(now);
+                commands.push((session_id, ClientEvent::Heard));
             }
+        }
+        _ => return Ok(()),
+    }
+    if let Some(fragment) = client_packet.message_as_fragment() {
+        if let Some(whole) = ingest.reassembler.insert(src_addr, &fragment) {
+            let client_packet = parse_client_packet(&whole)?;
+            // Fragments never nest; a reassembled message is always a complete packet.
+            if client_packet.message_type() == ClientMessage::Fragment {
+                return Err(Rejection::UnexpectedMessage(ClientMessage::Fragment));
+            }
+            return handle_reliable_packet(client_packet, session_id, commands, ingest, socket);
+        }
+        return Ok(());
+    }
+    handle_reliable_packet(client_packet, session_id, commands, ingest, socket)
+}
 
-            let v_overlap = p1.pos.y <= p2.pos.y + p2.size && p2.pos.y <= p1.pos.y + p1.size;
-            let h_overlap = p1.pos.x <= p2.pos.x + p2.size && p2.pos.x <= p1.pos.x + p1.size;
-            let overlap = v_overlap && h_overlap;
+fn handle_reliable_packet(client_packet: ClientPacket,
+                          session_id: u64,
+                          commands: &mut MutexGuard<Vec<(u64, ClientEvent)>>,
+                          ingest: &mut Ingest,
+                          socket: &UdpSocket) -> std::result::Result<(), Rejection> {
+    let Some(reliable) = client_packet.message_as_reliable() else {
+        return handle_client_packet(client_packet, session_id, commands, ingest, socket);
+    };
+    let Some(payload) = reliable.payload() else {
+        return Err(Rejection::UnexpectedMessage(ClientMessage::Reliable));
+    };
+    let Some(session) = ingest.sessions.get_mut(session_id) else {
+        return Ok(());
+    };
 
-            let p1_top = overlap && p1.vel.y > p2.vel.y;
-            let p1_bottom = overlap && p1.vel.y < p2.vel.y;
-            let p1_left = overlap && p1.vel.x > p2.vel.x;
-            let p1_right = overlap && p1.vel.x < p2.vel.x;
+    let delivered = session.reliable.receive(reliable.sequence(), payload.bytes());
+    // Duplicates are acknowledged again, in case our previous ack was the one that got lost.
+    let mut builder = FlatBufferBuilder::with_capacity(64);
+    reliable::write_ack(&mut builder, session.reliable.ack());
+    let _ = session.send(socket, builder.finished_data());
 
-            if overlap {
-                /*if p1_top {
-                    let force = Vec2 { x: (p1.vel.x + p2.vel.x) / 2.0, y: 0.0 };
-                    let pos = Vec2 { x: p1.pos.x, y: p2.pos.y - p1.size };
-                    player_forces.push((i, force, pos));
-                } else*/ if p1_left {
-                    let force = Vec2 { x: (p1.vel.x + p2.vel.x) / 2.0, y: (p1.vel.y + p2.vel.y) / 2.0 };
-                    let pos = Vec2 { x: p2.pos.x - p1.size, y: p1.pos.y };
-                    player_forces.push((i, force, pos));
-                } else if p1_right {
-                    let force = Vec2 { x: (p1.vel.x + p2.vel.x) / 2.0, y: (p1.vel.y + p2.vel.y) / 2.0 };
-                    let pos = Vec2 { x: p1.pos.x, y: p1.pos.y };
-                    player_forces.push((i, force, pos));
+    // One bad message doesn't stop the ones queued behind it; the first rejection is still reported.
+    let mut result = Ok(());
+    for payload in delivered {
+        let handled = parse_client_packet(&payload).and_then(|client_packet| {
+            match client_packet.message_type() {
+                ClientMessage::Fragment | ClientMessage::Reliable => {
+                    Err(Rejection::UnexpectedMessage(client_packet.message_type()))
                 }
+                _ => handle_client_packet(client_packet, session_id, commands, ingest, socket),
             }
+        });
+        if result.is_ok() {
+            result = handled;
         }
     }
-    player_forces
+    result
 }
 
-fn physics(players: &mut [Player]) {
-    for player in players {
-        player.pos.x = player.pos.x + player.vel.x;
-        player.pos.y = player.pos.y + player.vel.y;
-        player.vel.x *= FRICTION;
-        player.vel.y += GRAVITY;
-        player.jump_timer += 0.16;
+/// Answers a Connect without a valid cookie with a Challenge, and only lets one with a valid
+/// cookie through to the tick thread. Connect must arrive on its own, not fragmented or reliable,
+/// since those need a session.
+fn handle_connect_request(connect: Connect,
+                          packet_len: usize,
+                          token: u64,
+                          src_addr: SocketAddr,
+                          commands: &mut MutexGuard<Vec<(u64, ClientEvent)>>,
+                          ingest: &mut Ingest,
+                          socket: &UdpSocket) {
+    if packet_len < MIN_CONNECT_SIZE {
+        return;
+    }
+    let cookie = connect.cookie().map(|c| c.bytes()).unwrap_or_default();
+    if !ingest.cookies.verify(&src_addr, cookie) {
+        send_challenge(socket, &src_addr, &ingest.cookies.issue(&src_addr));
+        return;
+    }
+
+    // A token resumes its session, from a new address if need be. In secure mode the token travels
+    // in the clear, so only a sealed packet can move a session there.
+    let resumed = ingest.sessions.get(token)
+        .filter(|session| session.addr == src_addr || ingest.secure.is_none())
+        .map(|_| token);
+    // Without one, a repeated Connect from a known address means our response got lost.
+    let existing = resumed.or_else(|| ingest.sessions.id_for_addr(&src_addr));
+
+    let exchange = match &ingest.secure {
+        None => None,
+        Some(config) => {
+            let current = existing.and_then(|id| ingest.sessions.get(id)).and_then(|s| s.secure.as_ref());
+            // A secure server has no way to talk to a client that didn't send a usable key.
+            let Some(exchange) = key_exchange(&connect, current, config) else {
+                return;
+            };
+            Some(exchange)
+        }
+    };
+
+    let session_id = match existing {
+        Some(session_id) => session_id,
+        None => {
+            ingest.sessions.prune(ingest.player_timeout);
+            let session_id = match new_session_id() {
+                Ok(session_id) => session_id,
+                Err(e) => {
+                    println!("Can't generate a session token: {}", e);
+                    return;
+                }
+            };
+            ingest.sessions.insert(session_id, Session::new(src_addr));
+            session_id
+        }
+    };
+    ingest.sessions.migrate(session_id, src_addr);
+    let secure = exchange.map(|(link, fresh)| {
+        if let Some(fresh) = fresh {
+            ingest.sessions.set_secure(session_id, fresh);
+        }
+        link
+    });
+    if let Some(session) = ingest.sessions.get_mut(session_id) {
+        session.last_heard = Instant::now();
+        if resumed.is_none() {
+            // A client without its token is starting over, so its inputs and reliable messages
+            // count from scratch.
+            session.input_sequence.reset();
+            session.reliable = ReliableReceiver::new();
+        }
+    }
+    let room = connect.room().filter(|room| !room.is_empty()).unwrap_or(DEFAULT_ROOM).to_string();
+    let request = ConnectRequest { addr: src_addr, secure, room, fresh: resumed.is_none() };
+    commands.push((session_id, ClientEvent::Connect(request)))
+}
+
+/// The secure link to answer a Connect with: the current one if the client sent the same key again,
+/// so a lost ConnectResponse doesn't leave it holding keys we've replaced, or else a fresh exchange.
+fn key_exchange(connect: &Connect,
+                current: Option<&SecureSession>,
+                config: &SecureConfig) -> Option<(SecureLink, Option<SecureSession>)> {
+    let client_public_key = connect.public_key()?.bytes();
+    if let Some(current) = current.filter(|s| s.client_public_key[..] == *client_public_key) {
+        return Some((current.link.clone(), None));
+    }
+    let fresh = secure::accept(client_public_key, config)?;
+    Some((fresh.link.clone(), Some(fresh)))
+}
+
+/// Passes an input frame to the tick thread unless a newer one has already been seen.
+fn queue_input(session_id: u64,
+               frame: InputFrame,
+               target_tick: u64,
+               ack_tick: u64,
+               commands: &mut MutexGuard<Vec<(u64, ClientEvent)>>,
+               ingest: &mut Ingest) {
+    let Some(session) = ingest.sessions.get_mut(session_id) else {
+        return;
+    };
+    if !session.input_sequence.accept(frame.sequence) {
+        return;
+    }
+    if ack_tick != 0 {
+        commands.push((session_id, ClientEvent::SnapshotAck(ack_tick)));
+    }
+    commands.push((session_id, ClientEvent::Input(target_tick, frame)));
+}
 
-        if player.pos.y > SCREEN_HEIGHT as f32 - player.size {
-            player.pos.y = SCREEN_HEIGHT as f32 - player.size;
-            player.vel.y = 0.0;
+fn handle_client_packet(client_packet: ClientPacket,
+                        session_id: u64,
+                        commands: &mut MutexGuard<Vec<(u64, ClientEvent)>>,
+                        ingest: &mut Ingest,
+                        socket: &UdpSocket) -> std::result::Result<(), Rejection> {
+    match client_packet.message_type() {
+        ClientMessage::Disconnect => {
+            ingest.sessions.remove(session_id);
+            commands.push((session_id, ClientEvent::Disconnect));
+        }
+        ClientMessage::PlayerCommands => {
+            if let Some(player_commands) = client_packet.message_as_player_commands() {
+                let frame = InputFrame {
+                    sequence: player_commands.sequence(),
+                    input: Input::Commands(player_commands.commands().map(|list| list.iter().collect()).unwrap_or_default()),
+                };
+                queue_input(session_id, frame, player_commands.tick(), player_commands.ack_tick(), commands, ingest);
+            }
         }
-        if player.pos.y < 0.0 {
-            player.pos.y = 0.0;
-            player.vel.y = 0.0;
+        ClientMessage::PlayerInput => {
+            if let Some(player_input) = client_packet.message_as_player_input() {
+                let frame = InputFrame {
+                    sequence: player_input.sequence(),
+                    input: Input::Held(Held::new(
+                        player_input.buttons(),
+                        player_input.move_x(),
+                        player_input.aim_x(),
+                        player_input.aim_y(),
+                    )),
+                };
+                queue_input(session_id, frame, player_input.tick(), player_input.ack_tick(), commands, ingest);
+            }
         }
-        if player.pos.x > SCREEN_WIDTH as f32 - player.size {
-            player.pos.x = SCREEN_WIDTH as f32 - player.size;
-            player.vel.x = 0.0;
+        ClientMessage::ReliableAck => {
+            if let Some(reliable_ack) = client_packet.message_as_reliable_ack() {
+                commands.push((session_id, ClientEvent::ReliableAck(reliable_ack.ack())));
+            }
         }
-        if player.pos.x < 0.0 {
-            player.pos.x = 0.0;
-            player.vel.x = 0.0;
+        ClientMessage::Ping => {
+            let (Some(ping), Some(session)) = (client_packet.message_as_ping(), ingest.sessions.get(session_id)) else {
+                return Ok(());
+            };
+            // Answered here rather than on the next tick, so the round trip isn't padded by up to a tick.
+            let now = ingest.started.elapsed().as_micros() as u64;
+            let mut builder = FlatBufferBuilder::with_capacity(64);
+            let pong = schema_generated::Pong::create(
+                &mut builder,
+                &schema_generated::PongArgs {
+                    client_time: ping.client_time(),
+                    server_time: now,
+                },
+            );
+            finish_server_packet(&mut builder, ServerMessage::Pong, pong.as_union_value());
+            let _ = session.send(socket, builder.finished_data());
+
+            // The ping also closes the exchange our previous Pong started, seen from the server's side.
+            if ping.last_pong_server_time() != 0 {
+                let sample = ClockSample {
+                    sent: ping.last_pong_server_time(),
+                    remote_received: ping.last_pong_received(),
+                    remote_sent: ping.client_time(),
+                    received: now,
+                };
+                commands.push((session_id, ClientEvent::ClockSample(sample)));
+            }
         }
+        ClientMessage::Chat => {
+            if let Some(text) = client_packet.message_as_chat().and_then(|chat| chat.text()) {
+                commands.push((session_id, ClientEvent::Chat(text.to_string())));
+            }
+        }
+        ClientMessage::Admin => {
+            let Some(admin) = client_packet.message_as_admin() else {
+                return Ok(());
+            };
+            let token = admin.token().unwrap_or_default();
+            let authorized = ingest.admin_token.as_ref()
+                .is_some_and(|expected| crypto::constant_time_eq(expected.as_bytes(), token.as_bytes()));
+            if !authorized {
+                return Err(Rejection::Unauthorized);
+            }
+            println!("Admin {:?} on player {} from session {}", admin.action(), admin.player_id(), session_id);
+            commands.push((session_id, ClientEvent::Admin(admin.action(), admin.player_id())));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn physics(players: &mut [Player], rooms: &[Room]) {
+    for player in players {
+        let map = &rooms[player.room].level.map;
+        // Collision finds out again what the player is standing on.
+        player.grounded = false;
+        let mut body = player.body();
+        map.move_body(&mut body, DT);
+        player.set_body(&body);
+        player.vel.x *= (-FRICTION * DT).exp();
+        player.vel.y += GRAVITY * DT;
+        player.jump_timer += DT;
     }
 }
 
-fn get_player_by_ip<'a>(ip: &SocketAddr, players: &'a mut MutexGuard<Vec<Player>>) -> Option<&'a mut Player> {
-    players.iter_mut().find(|p| p.ip == *ip)
+fn get_player_by_session<'a>(session_id: u64, players: &'a mut MutexGuard<Vec<Player>>) -> Option<&'a mut Player> {
+    players.iter_mut().find(|p| p.session_id == session_id)
 }
 
 fn handle_move_right(player: &mut Player) {
-    player.vel.x += player.acc;
+    handle_move(player, 1.0);
 }
 
 fn handle_move_left(player: &mut Player) {
-    player.vel.x -= player.acc;
+    handle_move(player, -1.0);
+}
+
+/// Accelerates for one tick in proportion to `axis`, from -1 (full left) to 1 (full right).
+fn handle_move(player: &mut Player, axis: f32) {
+    player.vel.x += player.acc * axis * DT;
 }
 
 fn handle_jump(player: &mut Player) {
-    if player.pos.y >= SCREEN_HEIGHT as f32 - player.size && player.jump_timer > JUMP_CD {
+    if player.grounded && player.jump_timer > JUMP_CD {
         player.vel.y -= player.jump_force;
         player.jump_timer = 0.0;
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema_generated::ServerPacket;
+    use flatbuffers::root;
+    use multi_server::level::{Trigger, TriggerLayer};
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    fn server_socket() -> UdpSocket {
+        UdpSocket::bind("127.0.0.1:0").unwrap()
+    }
+
+    fn arena() -> Room {
+        Room::new(DEFAULT_ROOM.to_string(), Level::parse("arena", DEFAULT_MAP).unwrap())
+    }
+
+    fn request(addr: SocketAddr, fresh: bool) -> ConnectRequest {
+        ConnectRequest { addr, secure: None, room: DEFAULT_ROOM.to_string(), fresh }
+    }
+
+    fn header(tick: u64) -> SnapshotHeader {
+        SnapshotHeader { tick, server_time: 0, input_lead: 0, last_processed_input: 0, your_id: 1 }
+    }
+
+    fn presence(id: u32, room: usize, x: f32, always_relevant: bool) -> Presence {
+        Presence { id, room, pos: Vec2 { x, y: 0.0 }, always_relevant }
+    }
+
+    // Connects sessions 1 to MAX_PLAYERS, which fills the room.
+    fn fill(players: &mut MutexGuard<Vec<Player>>, rooms: &mut [Room], socket: &UdpSocket) {
+        for session_id in 1..=MAX_PLAYERS as u64 {
+            handle_connect(session_id, &request(addr(session_id as u16), true), players, rooms, &mut vec![], socket);
+        }
+        assert_eq!(room_population(players, 0), MAX_PLAYERS);
+    }
+
+    fn connect_response(client: &UdpSocket) -> (ConnectStatus, RejectReason, u16) {
+        let mut buf = [0u8; 512];
+        let len = client.recv(&mut buf).unwrap();
+        let response = root::<ServerPacket>(&buf[..len]).unwrap().message_as_connect_response().unwrap();
+        (response.status(), response.reason(), response.queue_position())
+    }
+
+    fn queued_sessions(room: &Room) -> Vec<u64> {
+        room.join_queue.iter().map(|q| q.session_id).collect()
+    }
+
+    fn ingest() -> Ingest {
+        Ingest {
+            reassembler: Reassembler::new(),
+            offenders: Offenders::new(),
+            rate_limiter: RateLimiter::new(),
+            cookies: CookieJar::new().unwrap(),
+            sessions: Sessions::new(),
+            secure: None,
+            admin_token: None,
+            player_timeout: DEFAULT_PLAYER_TIMEOUT,
+            started: Instant::now(),
+            ended_sessions: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    #[test]
+    fn a_full_room_queues_clients_until_its_queue_is_full_too() {
+        let socket = server_socket();
+        let players = Mutex::new(Vec::new());
+        let mut players = players.lock().unwrap();
+        let mut rooms = vec![arena()];
+        fill(&mut players, &mut rooms, &socket);
+
+        let client = server_socket();
+        client.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+        let first = MAX_PLAYERS as u64 + 1;
+        handle_connect(first, &request(client.local_addr().unwrap(), true), &mut players, &mut rooms, &mut vec![], &socket);
+        assert_eq!(connect_response(&client), (ConnectStatus::Queued, RejectReason::ServerFull, 1));
+        for session_id in first + 1..first + JOIN_QUEUE_CAPACITY as u64 {
+            handle_connect(session_id, &request(addr(session_id as u16), true), &mut players, &mut rooms, &mut vec![], &socket);
+        }
+        // Resending Connect keeps the same place.
+        handle_connect(first, &request(client.local_addr().unwrap(), false), &mut players, &mut rooms, &mut vec![], &socket);
+        assert_eq!(connect_response(&client), (ConnectStatus::Queued, RejectReason::ServerFull, 1));
+
+        let latecomer = server_socket();
+        latecomer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+        let session_id = first + JOIN_QUEUE_CAPACITY as u64;
+        let mut ended_sessions = vec![];
+        handle_connect(session_id, &request(latecomer.local_addr().unwrap(), true), &mut players, &mut rooms, &mut ended_sessions, &socket);
+        assert_eq!(connect_response(&latecomer), (ConnectStatus::Rejected, RejectReason::ServerFull, 0));
+        assert_eq!(rooms[0].join_queue.len(), JOIN_QUEUE_CAPACITY);
+        assert!(get_player_by_session(session_id, &mut players).is_none());
+        assert_eq!(ended_sessions, vec![session_id]);
+    }
+
+    #[test]
+    fn clients_asking_for_an_unknown_room_lose_their_session() {
+        let socket = server_socket();
+        let players = Mutex::new(Vec::new());
+        let mut players = players.lock().unwrap();
+        let mut rooms = vec![arena()];
+        fill(&mut players, &mut rooms, &socket);
+        handle_connect(21, &request(addr(21), true), &mut players, &mut rooms, &mut vec![], &socket);
+        assert_eq!(queued_sessions(&rooms[0]), vec![21]);
+
+        let mut ended_sessions = vec![];
+        let nowhere = ConnectRequest { room: "nowhere".to_string(), ..request(addr(21), false) };
+        handle_connect(21, &nowhere, &mut players, &mut rooms, &mut ended_sessions, &socket);
+        assert_eq!(ended_sessions, vec![21]);
+        assert!(rooms[0].join_queue.is_empty());
+    }
+
+    #[test]
+    fn queued_clients_are_admitted_in_order() {
+        let socket = server_socket();
+        let players = Mutex::new(Vec::new());
+        let mut players = players.lock().unwrap();
+        let mut rooms = vec![arena()];
+        fill(&mut players, &mut rooms, &socket);
+        for session_id in [21, 22, 23] {
+            handle_connect(session_id, &request(addr(session_id as u16), true), &mut players, &mut rooms, &mut vec![], &socket);
+        }
+        assert_eq!(queued_sessions(&rooms[0]), vec![21, 22, 23]);
+
+        players.retain(|p| p.session_id > 2);
+        admit_queued_clients(&mut players, &mut rooms, DEFAULT_PLAYER_TIMEOUT, &socket);
+        assert!(get_player_by_session(21, &mut players).is_some());
+        assert!(get_player_by_session(22, &mut players).is_some());
+        assert_eq!(queued_sessions(&rooms[0]), vec![23]);
+        assert_eq!(room_population(&players, 0), MAX_PLAYERS);
+    }
+
+    #[test]
+    fn queued_clients_that_go_quiet_lose_their_place() {
+        let socket = server_socket();
+        let players = Mutex::new(Vec::new());
+        let mut players = players.lock().unwrap();
+        let mut rooms = vec![arena()];
+        fill(&mut players, &mut rooms, &socket);
+        for session_id in [21, 22] {
+            handle_connect(session_id, &request(addr(session_id as u16), true), &mut players, &mut rooms, &mut vec![], &socket);
+        }
+        let settings = Settings { player_timeout: DEFAULT_PLAYER_TIMEOUT, interest_radius: DEFAULT_INTEREST_RADIUS };
+        let long_ago = Instant::now() - settings.player_timeout - Duration::from_secs(1);
+        for queued in rooms[0].join_queue.iter_mut() {
+            queued.last_heard = long_ago;
+        }
+
+        // Any packet from a queued client counts, not only Connect.
+        players.retain(|p| p.session_id != 1);
+        let mut commands = vec![(22, ClientEvent::Heard)];
+        tick(&mut players, &mut commands, &mut rooms, &mut vec![], 1, &settings, &socket);
+        assert!(get_player_by_session(21, &mut players).is_none());
+        assert!(get_player_by_session(22, &mut players).is_some());
+        assert!(rooms[0].join_queue.is_empty());
+    }
+
+    #[test]
+    fn only_newer_input_frames_are_queued() {
+        let mut ingest = ingest();
+        ingest.sessions.insert(1, Session::new(addr(1)));
+        let commands = Mutex::new(Vec::new());
+        let mut commands = commands.lock().unwrap();
+        let frame = |sequence| InputFrame { sequence, input: Input::Held(Held::default()) };
+        // A duplicate and a frame that was overtaken are dropped, across the wrap as well.
+        for sequence in [u32::MAX - 1, u32::MAX - 1, 0, u32::MAX, 1] {
+            queue_input(1, frame(sequence), 10, 0, &mut commands, &mut ingest);
+        }
+        let queued: Vec<u32> = commands.iter().filter_map(|(_, event)| match event {
+            ClientEvent::Input(_, frame) => Some(frame.sequence),
+            _ => None,
+        }).collect();
+        assert_eq!(queued, vec![u32::MAX - 1, 0, 1]);
+        // Sessions the receive loop doesn't know aren't queued at all.
+        queue_input(2, frame(7), 10, 0, &mut commands, &mut ingest);
+        assert_eq!(commands.len(), 3);
+    }
+
+    #[test]
+    fn players_see_their_room_within_the_radius() {
+        let viewer = presence(1, 0, 0.0, false);
+        assert!(viewer.sees(&viewer, 100.0));
+        assert!(viewer.sees(&presence(2, 0, 100.0, false), 100.0));
+        assert!(!viewer.sees(&presence(2, 0, 101.0, false), 100.0));
+        assert!(!viewer.sees(&presence(2, 1, 10.0, false), 100.0));
+        // Always-relevant players are seen from anywhere in their room, but not from other rooms.
+        assert!(viewer.sees(&presence(2, 0, 5000.0, true), 100.0));
+        assert!(!viewer.sees(&presence(2, 1, 10.0, true), 100.0));
+    }
+
+    #[test]
+    fn spotlight_triggers_make_players_always_relevant() {
+        let mut room = arena();
+        let spotlight = Trigger {
+            name: String::new(),
+            kind: SPOTLIGHT_TRIGGER.to_string(),
+            x: 0.0,
+            y: 0.0,
+            width: 200.0,
+            height: room.level.map.pixel_height(),
+            properties: vec![],
+        };
+        room.level.trigger_layers.push(TriggerLayer { name: "zones".to_string(), triggers: vec![spotlight] });
+        let mut rooms = vec![room];
+        let socket = server_socket();
+        let players = Mutex::new(Vec::new());
+        let mut players = players.lock().unwrap();
+        handle_connect(1, &request(addr(1), true), &mut players, &mut rooms, &mut vec![], &socket);
+        players[0].pos = Vec2 { x: 100.0, y: 200.0 };
+        let settings = Settings { player_timeout: DEFAULT_PLAYER_TIMEOUT, interest_radius: DEFAULT_INTEREST_RADIUS };
+        tick(&mut players, &mut vec![], &mut rooms, &mut vec![], 1, &settings, &socket);
+        assert!(players[0].always_relevant);
+        players[0].pos.x += 300.0;
+        tick(&mut players, &mut vec![], &mut rooms, &mut vec![], 2, &settings, &socket);
+        assert!(!players[0].always_relevant);
+    }
+
+    #[test]
+    fn reconnecting_without_the_token_starts_the_channels_over() {
+        let socket = server_socket();
+        let players = Mutex::new(Vec::new());
+        let mut players = players.lock().unwrap();
+        let mut rooms = vec![arena()];
+        let mut builder = FlatBufferBuilder::new();
+        handle_connect(1, &request(addr(1), true), &mut players, &mut rooms, &mut vec![], &socket);
+
+        let player = &mut players[0];
+        player.reliable.send(player_left_packet(9, LeaveReason::Disconnected));
+        player.reliable.send(player_left_packet(10, LeaveReason::Disconnected));
+        assert_eq!(player.reliable.due_packets(&mut builder).len(), 2);
+        player.snapshots.write(&mut builder, &header(1), &[]);
+        player.snapshots.ack(1);
+        player.inputs.insert(5, InputFrame { sequence: 7, input: Input::Commands(vec![]) }, 1);
+        player.last_processed_input = 6;
+
+        // A client that only lost our ConnectResponse keeps everything.
+        handle_connect(1, &request(addr(1), false), &mut players, &mut rooms, &mut vec![], &socket);
+        assert_eq!(players[0].last_processed_input, 6);
+        assert_eq!(players[0].snapshots.write(&mut builder, &header(2), &[]).0, ServerMessage::PlayersDelta);
+
+        handle_connect(1, &request(addr(1), true), &mut players, &mut rooms, &mut vec![], &socket);
+        let player = &mut players[0];
+        assert_eq!(player.last_processed_input, 0);
+        assert!(player.inputs.pop(5).is_none());
+        // The client has no baseline any more, and its new reliable receiver expects sequence 0.
+        assert_eq!(player.snapshots.write(&mut builder, &header(3), &[]).0, ServerMessage::PlayersList);
+        player.reliable.send(player_left_packet(11, LeaveReason::Disconnected));
+        let packets = player.reliable.due_packets(&mut builder);
+        assert_eq!(packets.len(), 1);
+        let reliable = root::<ServerPacket>(&packets[0]).unwrap().message_as_reliable().unwrap();
+        assert_eq!(reliable.sequence(), 0);
+    }
+}
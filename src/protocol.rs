@@ -0,0 +1,21 @@
+use flatbuffers::{FlatBufferBuilder, UnionWIPOffset, WIPOffset};
+
+use crate::schema_generated::{self, ServerMessage, ServerPacketArgs};
+
+/// Bumped whenever the schema changes in a way clients built against the previous one can't read.
+pub const PROTOCOL_VERSION: u16 = 1;
+
+/// Wraps `message` in a ServerPacket stamped with our protocol version and finishes the buffer.
+pub fn finish_server_packet(builder: &mut FlatBufferBuilder,
+                            message_type: ServerMessage,
+                            message: WIPOffset<UnionWIPOffset>) {
+    let packet = schema_generated::ServerPacket::create(
+        builder,
+        &ServerPacketArgs {
+            message_type,
+            message: Some(message),
+            protocol_version: PROTOCOL_VERSION,
+        },
+    );
+    builder.finish(packet, None);
+}
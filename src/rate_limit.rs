@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+// A client normally sends one packet per frame; this leaves room for resends and handshakes.
+const SOURCE_PACKETS_PER_SEC: f32 = 120.0;
+const SOURCE_BURST: f32 = 30.0;
+// Shared by every port on one IP, so rotating source ports doesn't buy a fresh budget, with room
+// for a few clients behind the same NAT.
+const HOST_PACKETS_PER_SEC: f32 = 480.0;
+const HOST_BURST: f32 = 120.0;
+// Shared by every source, so a crowd of addresses can't outrun the tick thread either.
+const GLOBAL_PACKETS_PER_SEC: f32 = 4000.0;
+const GLOBAL_BURST: f32 = 1000.0;
+const MAX_TRACKED_SOURCES: usize = 4096;
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Classic token bucket: holds up to `capacity` tokens and refills at `rate` tokens per second.
+pub struct TokenBucket {
+    capacity: f32,
+    rate: f32,
+    tokens: f32,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: f32, rate: f32) -> TokenBucket {
+        TokenBucket {
+            capacity,
+            rate,
+            tokens: capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Spends a token if there is one, after refilling for the time up to `now`.
+    pub fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f32();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+
+    fn is_full(&self, now: Instant) -> bool {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f32();
+        self.tokens + elapsed * self.rate >= self.capacity
+    }
+}
+
+struct Host {
+    bucket: TokenBucket,
+    // Packets from any of the host's ports dropped since the last report.
+    dropped: u64,
+}
+
+/// Per-source, per-host and global packet budgets for the receive loop. Packets over budget are
+/// dropped before they are parsed, and counted.
+pub struct RateLimiter {
+    sources: HashMap<SocketAddr, TokenBucket>,
+    hosts: HashMap<IpAddr, Host>,
+    global: TokenBucket,
+    dropped_by_source: u64,
+    dropped_by_host: u64,
+    dropped_by_global: u64,
+    last_report: Instant,
+}
+
+impl RateLimiter {
+    pub fn new() -> RateLimiter {
+        RateLimiter {
+            sources: HashMap::new(),
+            hosts: HashMap::new(),
+            global: TokenBucket::new(GLOBAL_BURST, GLOBAL_PACKETS_PER_SEC),
+            dropped_by_source: 0,
+            dropped_by_host: 0,
+            dropped_by_global: 0,
+            last_report: Instant::now(),
+        }
+    }
+
+    /// Whether a packet from `addr` arriving at `now` fits in the budget. Each call spends a token.
+    pub fn allow(&mut self, addr: SocketAddr, now: Instant) -> bool {
+        let untracked = !self.sources.contains_key(&addr) || !self.hosts.contains_key(&addr.ip());
+        if untracked && (self.sources.len() >= MAX_TRACKED_SOURCES || self.hosts.len() >= MAX_TRACKED_SOURCES) {
+            // Buckets that are full haven't been drawn on recently, so nothing is lost by forgetting them.
+            self.sources.retain(|_, bucket| !bucket.is_full(now));
+            self.hosts.retain(|_, host| !host.bucket.is_full(now));
+            if self.sources.len() >= MAX_TRACKED_SOURCES || self.hosts.len() >= MAX_TRACKED_SOURCES {
+                self.dropped_by_global += 1;
+                return false;
+            }
+        }
+
+        // A flooding host is cut off by its own buckets before it can drain the global one, however
+        // many ports it sends from.
+        let host = self.hosts.entry(addr.ip()).or_insert_with(|| Host {
+            bucket: TokenBucket::new(HOST_BURST, HOST_PACKETS_PER_SEC),
+            dropped: 0,
+        });
+        let source = self.sources.entry(addr).or_insert_with(|| TokenBucket::new(SOURCE_BURST, SOURCE_PACKETS_PER_SEC));
+        if !host.bucket.try_take(now) {
+            host.dropped += 1;
+            self.dropped_by_host += 1;
+            return false;
+        }
+        if !source.try_take(now) {
+            host.dropped += 1;
+            self.dropped_by_source += 1;
+            return false;
+        }
+        if !self.global.try_take(now) {
+            self.dropped_by_global += 1;
+            return false;
+        }
+        true
+    }
+
+    /// Counts a packet dropped because the tick thread's event queue is full.
+    pub fn record_overflow(&mut self) {
+        self.dropped_by_global += 1;
+    }
+
+    /// Logs how many packets were dropped since the last report, at most once per `REPORT_INTERVAL`.
+    pub fn report(&mut self) {
+        if self.last_report.elapsed() < REPORT_INTERVAL {
+            return;
+        }
+        self.last_report = Instant::now();
+        if self.dropped_by_source == 0 && self.dropped_by_host == 0 && self.dropped_by_global == 0 {
+            return;
+        }
+
+        let worst = self.hosts.iter().max_by_key(|(_, h)| h.dropped).filter(|(_, h)| h.dropped > 0);
+        match worst {
+            Some((ip, host)) => println!(
+                "Rate limited {} packets per source, {} per host (worst: {} with {}) and {} over the global budget",
+                self.dropped_by_source, self.dropped_by_host, ip, host.dropped, self.dropped_by_global
+            ),
+            None => println!("Rate limited {} packets over the global budget", self.dropped_by_global),
+        }
+        self.dropped_by_source = 0;
+        self.dropped_by_host = 0;
+        self.dropped_by_global = 0;
+        for host in self.hosts.values_mut() {
+            host.dropped = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    // One port on each of many hosts.
+    fn host(index: usize) -> SocketAddr {
+        SocketAddr::from(((0x0a00_0000 + index as u32).to_be_bytes(), 1))
+    }
+
+    fn allowed(limiter: &mut RateLimiter, addr: SocketAddr, packets: usize, now: Instant) -> usize {
+        (0..packets).filter(|_| limiter.allow(addr, now)).count()
+    }
+
+    #[test]
+    fn buckets_refill_up_to_their_burst() {
+        let mut bucket = TokenBucket::new(3.0, 10.0);
+        let start = bucket.last_refill;
+        assert_eq!((0..5).filter(|_| bucket.try_take(start)).count(), 3);
+        // A tenth of a second buys one more token.
+        assert!(bucket.try_take(start + Duration::from_millis(100)));
+        assert!(!bucket.try_take(start + Duration::from_millis(100)));
+        // A long pause only refills to the burst size.
+        assert_eq!((0..10).filter(|_| bucket.try_take(start + Duration::from_secs(10))).count(), 3);
+    }
+
+    #[test]
+    fn a_flooding_source_does_not_use_up_other_sources_budgets() {
+        let mut limiter = RateLimiter::new();
+        let now = limiter.global.last_refill;
+        assert_eq!(allowed(&mut limiter, addr(1), 100, now), SOURCE_BURST as usize);
+        assert_eq!(allowed(&mut limiter, addr(2), 1, now), 1);
+        assert_eq!(limiter.hosts[&addr(1).ip()].dropped, 100 - SOURCE_BURST as u64);
+        assert_eq!(limiter.dropped_by_global, 0);
+    }
+
+    #[test]
+    fn rotating_ports_does_not_get_past_the_host_budget() {
+        let mut limiter = RateLimiter::new();
+        let now = limiter.global.last_refill;
+        let total: usize = (0..100).map(|port| allowed(&mut limiter, addr(port), SOURCE_BURST as usize, now)).sum();
+        assert_eq!(total, HOST_BURST as usize);
+        assert_eq!(limiter.dropped_by_host as usize, 100 * SOURCE_BURST as usize - HOST_BURST as usize);
+        // Other hosts keep their own budgets.
+        assert_eq!(allowed(&mut limiter, host(1), 1, now), 1);
+    }
+
+    #[test]
+    fn the_global_budget_caps_many_sources_together() {
+        let mut limiter = RateLimiter::new();
+        let now = limiter.global.last_refill;
+        let per_source = SOURCE_BURST as usize;
+        let sources = GLOBAL_BURST as usize / per_source + 2;
+        let total: usize = (0..sources).map(|index| allowed(&mut limiter, host(index), per_source, now)).sum();
+        assert_eq!(total, GLOBAL_BURST as usize);
+        assert_eq!(limiter.dropped_by_global as usize, sources * per_source - GLOBAL_BURST as usize);
+        assert_eq!(limiter.dropped_by_source, 0);
+    }
+
+    #[test]
+    fn idle_sources_are_forgotten_to_make_room() {
+        let mut limiter = RateLimiter::new();
+        let start = limiter.global.last_refill;
+        for index in 0..MAX_TRACKED_SOURCES {
+            limiter.allow(host(index), start);
+        }
+        assert_eq!(limiter.sources.len(), MAX_TRACKED_SOURCES);
+        // Every tracked source is still active, so there's no room for a new one.
+        let newcomer = host(MAX_TRACKED_SOURCES);
+        assert!(!limiter.allow(newcomer, start));
+        assert!(!limiter.sources.contains_key(&newcomer));
+
+        // Once their buckets have refilled they are idle and make way.
+        assert!(limiter.allow(newcomer, start + Duration::from_secs(1)));
+        assert_eq!(limiter.sources.len(), 1);
+        assert_eq!(limiter.hosts.len(), 1);
+    }
+}
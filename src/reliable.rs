@@ -0,0 +1,313 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use flatbuffers::FlatBufferBuilder;
+
+use crate::protocol::finish_server_packet;
+use crate::schema_generated::{Reliable, ReliableAck, ReliableAckArgs, ReliableArgs, ServerMessage};
+use crate::sequence::Sequence;
+
+const RESEND_INTERVAL: Duration = Duration::from_millis(100);
+// Messages beyond this many unacknowledged ones wait their turn, which keeps sequence
+// numbers from wrapping into the receiver's window.
+const SEND_WINDOW: usize = 256;
+// Messages queued behind a full window. A peer this far behind has stalled, and more is dropped.
+const MAX_WAITING: usize = 1024;
+// Resends per `due_packets` call, so a peer that stops acking can't have the whole window resent
+// every interval.
+const MAX_RESENDS_PER_CALL: usize = 8;
+// A peer that leaves the oldest message unacknowledged this long has stopped acking.
+const STALL_TIMEOUT: Duration = Duration::from_secs(10);
+const RECEIVE_WINDOW: u16 = 256;
+
+struct InFlight {
+    sequence: u16,
+    payload: Vec<u8>,
+    first_sent: Option<Instant>,
+    last_sent: Option<Instant>,
+}
+
+/// Sending half of a reliable, ordered channel to one peer.
+///
+/// Every message is resent until the peer acknowledges it, so game code can queue events that
+/// must arrive, like players leaving, and forget about them.
+pub struct ReliableSender {
+    next_sequence: u16,
+    in_flight: VecDeque<InFlight>,
+    waiting: VecDeque<Vec<u8>>,
+}
+
+impl ReliableSender {
+    pub fn new() -> ReliableSender {
+        ReliableSender {
+            next_sequence: 0,
+            in_flight: VecDeque::new(),
+            waiting: VecDeque::new(),
+        }
+    }
+
+    /// Queues a finished ServerPacket for reliable delivery. Dropped if the peer has stalled so
+    /// badly that `MAX_WAITING` messages are already queued.
+    pub fn send(&mut self, payload: Vec<u8>) {
+        if self.waiting.len() >= MAX_WAITING {
+            return;
+        }
+        self.waiting.push_back(payload);
+        self.fill_window();
+    }
+
+    /// Whether the peer has stopped acknowledging: its oldest message has gone unacknowledged for
+    /// `STALL_TIMEOUT`, or the queue behind the window is full. Such a peer should be dropped.
+    pub fn stalled(&self, now: Instant) -> bool {
+        let oldest = self.in_flight.front().and_then(|message| message.first_sent);
+        self.waiting.len() >= MAX_WAITING || oldest.is_some_and(|sent| now.duration_since(sent) >= STALL_TIMEOUT)
+    }
+
+    /// Drops every message up to and including `ack`, which the peer has now received in order.
+    pub fn ack(&mut self, ack: u16) {
+        while let Some(front) = self.in_flight.front() {
+            if front.sequence != ack && !ack.newer_than(front.sequence) {
+                break;
+            }
+            self.in_flight.pop_front();
+        }
+        self.fill_window();
+    }
+
+    /// Builds a Reliable packet for every message that has never been sent, and for up to
+    /// `MAX_RESENDS_PER_CALL` of the oldest ones due for a resend.
+    pub fn due_packets(&mut self, builder: &mut FlatBufferBuilder) -> Vec<Vec<u8>> {
+        let now = Instant::now();
+        let mut packets = vec![];
+        let mut resends = 0;
+        for message in self.in_flight.iter_mut() {
+            match message.last_sent {
+                Some(sent) if now.duration_since(sent) < RESEND_INTERVAL => continue,
+                Some(_) if resends == MAX_RESENDS_PER_CALL => continue,
+                Some(_) => resends += 1,
+                None => message.first_sent = Some(now),
+            }
+            message.last_sent = Some(now);
+            builder.reset();
+            write_reliable(builder, message.sequence, &message.payload);
+            packets.push(builder.finished_data().to_vec());
+        }
+        packets
+    }
+
+    fn fill_window(&mut self) {
+        while self.in_flight.len() < SEND_WINDOW {
+            let Some(payload) = self.waiting.pop_front() else {
+                break;
+            };
+            self.in_flight.push_back(InFlight {
+                sequence: self.next_sequence,
+                payload,
+                first_sent: None,
+                last_sent: None,
+            });
+            self.next_sequence = self.next_sequence.wrapping_add(1);
+        }
+    }
+}
+
+/// Receiving half of a reliable, ordered channel from one peer.
+pub struct ReliableReceiver {
+    next_expected: u16,
+    buffered: HashMap<u16, Vec<u8>>,
+}
+
+impl ReliableReceiver {
+    pub fn new() -> ReliableReceiver {
+        ReliableReceiver {
+            next_expected: 0,
+            buffered: HashMap::new(),
+        }
+    }
+
+    /// Accepts one message and returns every payload that is now deliverable, in order.
+    /// Duplicates and messages too far ahead of the window are dropped.
+    pub fn receive(&mut self, sequence: u16, payload: &[u8]) -> Vec<Vec<u8>> {
+        let ahead = sequence.wrapping_sub(self.next_expected);
+        if ahead >= RECEIVE_WINDOW {
+            return vec![];
+        }
+        self.buffered.entry(sequence).or_insert_with(|| payload.to_vec());
+
+        let mut delivered = vec![];
+        while let Some(payload) = self.buffered.remove(&self.next_expected) {
+            delivered.push(payload);
+            self.next_expected = self.next_expected.wrapping_add(1);
+        }
+        delivered
+    }
+
+    /// Cumulative acknowledgement: the last sequence received with nothing missing before it.
+    pub fn ack(&self) -> u16 {
+        self.next_expected.wrapping_sub(1)
+    }
+}
+
+fn write_reliable(builder: &mut FlatBufferBuilder, sequence: u16, payload: &[u8]) {
+    let payload = builder.create_vector(payload);
+    let reliable = Reliable::create(builder, &ReliableArgs { sequence, payload: Some(payload) });
+    finish_server_packet(builder, ServerMessage::Reliable, reliable.as_union_value());
+}
+
+pub fn write_ack(builder: &mut FlatBufferBuilder, ack: u16) {
+    let reliable_ack = ReliableAck::create(builder, &ReliableAckArgs { ack });
+    finish_server_packet(builder, ServerMessage::ReliableAck, reliable_ack.as_union_value());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema_generated::ServerPacket;
+    use flatbuffers::root;
+
+    fn sequences(sender: &ReliableSender) -> Vec<u16> {
+        sender.in_flight.iter().map(|m| m.sequence).collect()
+    }
+
+    #[test]
+    fn out_of_order_messages_are_delivered_in_order() {
+        let mut receiver = ReliableReceiver::new();
+        assert!(receiver.receive(2, b"c").is_empty());
+        assert!(receiver.receive(1, b"b").is_empty());
+        assert_eq!(receiver.ack(), u16::MAX);
+        assert_eq!(receiver.receive(0, b"a"), vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]);
+        assert_eq!(receiver.ack(), 2);
+    }
+
+    #[test]
+    fn duplicates_are_delivered_once() {
+        let mut receiver = ReliableReceiver::new();
+        assert!(receiver.receive(1, b"b").is_empty());
+        // A resend of a buffered message keeps the first copy.
+        assert!(receiver.receive(1, b"x").is_empty());
+        assert_eq!(receiver.receive(0, b"a"), vec![b"a".to_vec(), b"b".to_vec()]);
+        assert!(receiver.receive(0, b"a").is_empty());
+        assert!(receiver.receive(1, b"b").is_empty());
+        assert_eq!(receiver.ack(), 1);
+    }
+
+    #[test]
+    fn messages_beyond_the_window_are_dropped() {
+        let mut receiver = ReliableReceiver::new();
+        assert!(receiver.receive(RECEIVE_WINDOW, b"far").is_empty());
+        assert!(receiver.receive(RECEIVE_WINDOW - 1, b"last").is_empty());
+        let delivered: Vec<Vec<u8>> = (0..RECEIVE_WINDOW - 1).flat_map(|sequence| receiver.receive(sequence, b"")).collect();
+        assert_eq!(delivered.len(), RECEIVE_WINDOW as usize);
+        assert_eq!(delivered.last().unwrap(), b"last");
+        assert_eq!(receiver.ack(), RECEIVE_WINDOW - 1);
+    }
+
+    #[test]
+    fn receiving_carries_on_across_wraparound() {
+        let mut receiver = ReliableReceiver::new();
+        receiver.next_expected = u16::MAX - 1;
+        assert!(receiver.receive(0, b"c").is_empty());
+        assert_eq!(receiver.receive(u16::MAX - 1, b"a"), vec![b"a".to_vec()]);
+        assert_eq!(receiver.receive(u16::MAX, b"b"), vec![b"b".to_vec(), b"c".to_vec()]);
+        assert_eq!(receiver.ack(), 0);
+        // Old messages from before the wrap look far behind, not far ahead.
+        assert!(receiver.receive(u16::MAX, b"b").is_empty());
+    }
+
+    #[test]
+    fn acks_are_cumulative_and_stale_ones_are_ignored() {
+        let mut sender = ReliableSender::new();
+        for payload in 0..5u8 {
+            sender.send(vec![payload]);
+        }
+        sender.ack(2);
+        assert_eq!(sequences(&sender), vec![3, 4]);
+        sender.ack(1);
+        assert_eq!(sequences(&sender), vec![3, 4]);
+        sender.ack(4);
+        assert!(sequences(&sender).is_empty());
+    }
+
+    #[test]
+    fn acks_carry_on_across_wraparound() {
+        let mut sender = ReliableSender::new();
+        sender.next_sequence = u16::MAX - 1;
+        for payload in 0..4u8 {
+            sender.send(vec![payload]);
+        }
+        assert_eq!(sequences(&sender), vec![u16::MAX - 1, u16::MAX, 0, 1]);
+        sender.ack(u16::MAX);
+        assert_eq!(sequences(&sender), vec![0, 1]);
+        sender.ack(0);
+        assert_eq!(sequences(&sender), vec![1]);
+    }
+
+    #[test]
+    fn messages_past_the_send_window_wait_for_acks() {
+        let mut sender = ReliableSender::new();
+        for _ in 0..SEND_WINDOW + 10 {
+            sender.send(vec![]);
+        }
+        assert_eq!(sender.in_flight.len(), SEND_WINDOW);
+        assert_eq!(sender.waiting.len(), 10);
+
+        let mut builder = FlatBufferBuilder::new();
+        assert_eq!(sender.due_packets(&mut builder).len(), SEND_WINDOW);
+        // Nothing is due again until the resend interval has passed.
+        assert!(sender.due_packets(&mut builder).is_empty());
+
+        sender.ack(3);
+        assert_eq!(sender.in_flight.len(), SEND_WINDOW);
+        assert_eq!(sender.waiting.len(), 6);
+        assert_eq!(sender.in_flight.back().unwrap().sequence, SEND_WINDOW as u16 + 3);
+        // Only the messages that just entered the window go out.
+        assert_eq!(sender.due_packets(&mut builder).len(), 4);
+    }
+
+    #[test]
+    fn resends_are_limited_per_call() {
+        let mut sender = ReliableSender::new();
+        for _ in 0..20 {
+            sender.send(vec![]);
+        }
+        let mut builder = FlatBufferBuilder::new();
+        assert_eq!(sender.due_packets(&mut builder).len(), 20);
+        for message in sender.in_flight.iter_mut() {
+            message.last_sent = message.last_sent.map(|sent| sent - RESEND_INTERVAL);
+        }
+        // The oldest go first, and new messages aren't held back by the limit.
+        sender.send(vec![]);
+        let packets = sender.due_packets(&mut builder);
+        assert_eq!(packets.len(), MAX_RESENDS_PER_CALL + 1);
+        let first = root::<ServerPacket>(&packets[0]).unwrap().message_as_reliable().unwrap().sequence();
+        assert_eq!(first, 0);
+        assert_eq!(sender.due_packets(&mut builder).len(), MAX_RESENDS_PER_CALL);
+    }
+
+    #[test]
+    fn a_peer_that_stops_acking_stalls() {
+        let mut sender = ReliableSender::new();
+        let now = Instant::now();
+        assert!(!sender.stalled(now));
+        sender.send(vec![]);
+        // Unsent messages don't count against the peer.
+        assert!(!sender.stalled(now + STALL_TIMEOUT));
+        sender.due_packets(&mut FlatBufferBuilder::new());
+        let sent = sender.in_flight[0].first_sent.unwrap();
+        assert!(!sender.stalled(sent + STALL_TIMEOUT / 2));
+        assert!(sender.stalled(sent + STALL_TIMEOUT));
+        sender.ack(0);
+        assert!(!sender.stalled(sent + STALL_TIMEOUT));
+    }
+
+    #[test]
+    fn the_queue_behind_the_window_is_capped() {
+        let mut sender = ReliableSender::new();
+        for _ in 0..SEND_WINDOW + MAX_WAITING + 5 {
+            sender.send(vec![]);
+        }
+        assert_eq!(sender.in_flight.len(), SEND_WINDOW);
+        assert_eq!(sender.waiting.len(), MAX_WAITING);
+        assert!(sender.stalled(Instant::now()));
+    }
+}
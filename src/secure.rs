@@ -0,0 +1,305 @@
+//! Optional secure mode: an X25519 key exchange during Connect, then every datagram in either
+//! direction sealed with ChaCha20-Poly1305.
+//!
+//! A sealed datagram is the 8-byte key id and 8-byte nonce counter, both little-endian, followed by
+//! the encrypted packet and its tag. The header is authenticated as associated data. Without a
+//! pre-shared key the exchange is anonymous, which keeps off-path and passive attackers out; with
+//! one, only clients that know the key can complete it.
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::crypto::chacha20poly1305::{self, KEY_SIZE, NONCE_SIZE, TAG_SIZE};
+use crate::crypto::hmac_sha256;
+use crate::crypto::x25519;
+
+pub const HEADER_SIZE: usize = 16;
+/// Bytes sealing adds to a packet.
+pub const OVERHEAD: usize = HEADER_SIZE + TAG_SIZE;
+// Sealed datagrams this far behind the newest one are dropped even if they were never seen.
+const REPLAY_WINDOW: u64 = 64;
+
+#[derive(Clone, Copy)]
+enum Direction {
+    ClientToServer = 0,
+    ServerToClient = 1,
+}
+
+fn nonce(direction: Direction, counter: u64) -> [u8; NONCE_SIZE] {
+    let mut nonce = [0u8; NONCE_SIZE];
+    nonce[..4].copy_from_slice(&(direction as u32).to_le_bytes());
+    nonce[4..].copy_from_slice(&counter.to_le_bytes());
+    nonce
+}
+
+/// Settings for secure mode, which applies to every client once enabled.
+pub struct SecureConfig {
+    pub psk: Option<[u8; KEY_SIZE]>,
+}
+
+/// Parses a 32-byte key written as 64 hex digits.
+pub fn parse_key(hex: &str) -> Option<[u8; KEY_SIZE]> {
+    let hex = hex.trim();
+    if hex.len() != KEY_SIZE * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let mut key = [0u8; KEY_SIZE];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(key)
+}
+
+/// Seals outgoing packets for one peer. Shared between threads, so the counter is atomic.
+pub struct Sealer {
+    key: [u8; KEY_SIZE],
+    key_id: u64,
+    direction: Direction,
+    next_counter: AtomicU64,
+}
+
+impl Sealer {
+    fn new(key: [u8; KEY_SIZE], key_id: u64, direction: Direction) -> Sealer {
+        Sealer { key, key_id, direction, next_counter: AtomicU64::new(0) }
+    }
+
+    pub fn seal(&self, packet: &[u8]) -> Vec<u8> {
+        let counter = self.next_counter.fetch_add(1, Ordering::Relaxed);
+        let mut datagram = Vec::with_capacity(packet.len() + OVERHEAD);
+        datagram.extend_from_slice(&self.key_id.to_le_bytes());
+        datagram.extend_from_slice(&counter.to_le_bytes());
+        datagram.extend_from_slice(packet);
+        let (header, body) = datagram.split_at_mut(HEADER_SIZE);
+        let tag = chacha20poly1305::seal(&self.key, &nonce(self.direction, counter), header, body);
+        datagram.extend_from_slice(&tag);
+        datagram
+    }
+}
+
+/// Opens incoming datagrams from one peer and drops replays.
+pub struct Opener {
+    key: [u8; KEY_SIZE],
+    key_id: u64,
+    direction: Direction,
+    // One past the highest counter accepted so far.
+    next_counter: u64,
+    // Bit i is set once counter `next_counter - 1 - i` has been accepted.
+    seen: u64,
+}
+
+impl Opener {
+    fn new(key: [u8; KEY_SIZE], key_id: u64, direction: Direction) -> Opener {
+        Opener { key, key_id, direction, next_counter: 0, seen: 0 }
+    }
+
+    fn is_fresh(&self, counter: u64) -> bool {
+        if counter >= self.next_counter {
+            return true;
+        }
+        let age = self.next_counter - 1 - counter;
+        age < REPLAY_WINDOW && self.seen & (1 << age) == 0
+    }
+
+    fn mark_seen(&mut self, counter: u64) {
+        if counter >= self.next_counter {
+            let shift = counter - self.next_counter + 1;
+            self.seen = if shift >= REPLAY_WINDOW { 0 } else { self.seen << shift };
+            self.seen |= 1;
+            self.next_counter = counter + 1;
+        } else {
+            self.seen |= 1 << (self.next_counter - 1 - counter);
+        }
+    }
+
+    /// Returns the packet inside `datagram` if it was sealed with this session's key and hasn't
+    /// been seen before.
+    pub fn open(&mut self, datagram: &[u8]) -> Option<Vec<u8>> {
+        if datagram.len() < OVERHEAD || key_id(datagram)? != self.key_id {
+            return None;
+        }
+        let counter = u64::from_le_bytes(datagram[8..HEADER_SIZE].try_into().unwrap());
+        if !self.is_fresh(counter) {
+            return None;
+        }
+        let (header, rest) = datagram.split_at(HEADER_SIZE);
+        let (body, tag) = rest.split_at(rest.len() - TAG_SIZE);
+        let mut packet = body.to_vec();
+        if !chacha20poly1305::open(&self.key, &nonce(self.direction, counter), header, &mut packet, tag) {
+            return None;
+        }
+        self.mark_seen(counter);
+        Some(packet)
+    }
+}
+
+/// The key id a sealed datagram claims to belong to.
+pub fn key_id(datagram: &[u8]) -> Option<u64> {
+    Some(u64::from_le_bytes(datagram.get(..8)?.try_into().unwrap()))
+}
+
+/// What the tick thread needs to talk to a secure client: the server's half of the key exchange,
+/// which goes back in ConnectResponse, and the key to seal with.
+#[derive(Clone)]
+pub struct SecureLink {
+    pub public_key: [u8; x25519::KEY_SIZE],
+    pub sealer: Arc<Sealer>,
+}
+
+/// Both directions of one client's secure session, as held by the receive thread.
+pub struct SecureSession {
+    pub client_public_key: [u8; x25519::KEY_SIZE],
+    pub link: SecureLink,
+    pub opener: Opener,
+}
+
+impl SecureSession {
+    pub fn key_id(&self) -> u64 {
+        self.opener.key_id
+    }
+}
+
+struct SessionKeys {
+    client_to_server: [u8; KEY_SIZE],
+    server_to_client: [u8; KEY_SIZE],
+    key_id: u64,
+}
+
+/// HKDF-SHA256 (RFC 5869) over the shared secret, with the pre-shared key as salt. Every output
+/// fits in a single block, and both public keys are bound into each one.
+fn derive_keys(shared: &[u8], client_public: &[u8], server_public: &[u8], psk: Option<&[u8; KEY_SIZE]>) -> SessionKeys {
+    let prk = hmac_sha256(psk.unwrap_or(&[0; KEY_SIZE]), &[shared]);
+    let expand = |label: &[u8]| hmac_sha256(&prk, &[label, client_public, server_public, &[1]]);
+    let key_id = expand(b"multi_server key id");
+    SessionKeys {
+        client_to_server: expand(b"multi_server client to server"),
+        server_to_client: expand(b"multi_server server to client"),
+        key_id: u64::from_le_bytes(key_id[..8].try_into().unwrap()),
+    }
+}
+
+/// Completes the server's side of the key exchange with the public key from a client's Connect.
+/// Returns None for keys that would give a predictable shared secret, and if the system can't
+/// provide the randomness for our side.
+pub fn accept(client_public_key: &[u8], config: &SecureConfig) -> Option<SecureSession> {
+    let client_public_key: [u8; x25519::KEY_SIZE] = client_public_key.try_into().ok()?;
+    let (secret, public_key) = match x25519::generate_keypair() {
+        Ok(keypair) => keypair,
+        Err(e) => {
+            println!("Can't generate a key pair: {}", e);
+            return None;
+        }
+    };
+    let shared = x25519::x25519(&secret, &client_public_key);
+    if shared == [0; x25519::KEY_SIZE] {
+        return None;
+    }
+
+    let keys = derive_keys(&shared, &client_public_key, &public_key, config.psk.as_ref());
+    Some(SecureSession {
+        client_public_key,
+        link: SecureLink {
+            public_key,
+            sealer: Arc::new(Sealer::new(keys.server_to_client, keys.key_id, Direction::ServerToClient)),
+        },
+        opener: Opener::new(keys.client_to_server, keys.key_id, Direction::ClientToServer),
+    })
+}
+
+/// Sends one datagram, sealed if the peer has a secure session.
+pub fn send_to(socket: &UdpSocket, addr: &SocketAddr, packet: &[u8], sealer: Option<&Sealer>) -> io::Result<()> {
+    match sealer {
+        Some(sealer) => socket.send_to(&sealer.seal(packet), addr)?,
+        None => socket.send_to(packet, addr)?,
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The client's side of `accept`.
+    fn client_keys(secret: &[u8; 32], public_key: &[u8; 32], link: &SecureLink, psk: Option<&[u8; 32]>) -> (Sealer, Opener) {
+        let shared = x25519::x25519(secret, &link.public_key);
+        let keys = derive_keys(&shared, public_key, &link.public_key, psk);
+        (
+            Sealer::new(keys.client_to_server, keys.key_id, Direction::ClientToServer),
+            Opener::new(keys.server_to_client, keys.key_id, Direction::ServerToClient),
+        )
+    }
+
+    fn handshake(psk: Option<[u8; 32]>) -> (SecureSession, Sealer, Opener) {
+        let (secret, public_key) = x25519::generate_keypair().unwrap();
+        let config = SecureConfig { psk };
+        let session = accept(&public_key, &config).unwrap();
+        let (sealer, opener) = client_keys(&secret, &public_key, &session.link, psk.as_ref());
+        (session, sealer, opener)
+    }
+
+    #[test]
+    fn both_directions_round_trip() {
+        let (mut session, client_sealer, mut client_opener) = handshake(None);
+        let sealed = client_sealer.seal(b"commands");
+        assert_eq!(sealed.len(), b"commands".len() + OVERHEAD);
+        assert_eq!(session.opener.open(&sealed).unwrap(), b"commands");
+
+        let sealed = session.link.sealer.seal(b"snapshot");
+        assert_eq!(client_opener.open(&sealed).unwrap(), b"snapshot");
+    }
+
+    #[test]
+    fn replayed_and_forged_datagrams_are_dropped() {
+        let (mut session, client_sealer, _) = handshake(None);
+        let first = client_sealer.seal(b"one");
+        let second = client_sealer.seal(b"two");
+        assert!(session.opener.open(&second).is_some());
+        // Reordering within the window is fine, but each counter only opens once.
+        assert!(session.opener.open(&first).is_some());
+        assert!(session.opener.open(&first).is_none());
+        assert!(session.opener.open(&second).is_none());
+
+        let mut forged = client_sealer.seal(b"move");
+        forged[HEADER_SIZE] ^= 1;
+        assert!(session.opener.open(&forged).is_none());
+        // Our own direction's datagrams can't be reflected back at us.
+        assert!(session.opener.open(&session.link.sealer.seal(b"echo")).is_none());
+        assert!(session.opener.open(b"plain").is_none());
+    }
+
+    #[test]
+    fn datagrams_older_than_the_window_are_dropped() {
+        let (mut session, client_sealer, _) = handshake(None);
+        let old = client_sealer.seal(b"old");
+        for _ in 0..REPLAY_WINDOW {
+            assert!(session.opener.open(&client_sealer.seal(b"new")).is_some());
+        }
+        assert!(session.opener.open(&old).is_none());
+    }
+
+    #[test]
+    fn wrong_pre_shared_key_cannot_talk() {
+        let (secret, public_key) = x25519::generate_keypair().unwrap();
+        let mut session = accept(&public_key, &SecureConfig { psk: Some([1; 32]) }).unwrap();
+        let (sealer, _) = client_keys(&secret, &public_key, &session.link, Some(&[2; 32]));
+        assert!(session.opener.open(&sealer.seal(b"hello")).is_none());
+
+        let (sealer, _) = client_keys(&secret, &public_key, &session.link, Some(&[1; 32]));
+        assert!(session.opener.open(&sealer.seal(b"hello")).is_some());
+    }
+
+    #[test]
+    fn low_order_public_key_is_refused() {
+        assert!(accept(&[0; 32], &SecureConfig { psk: None }).is_none());
+        assert!(accept(&[9; 31], &SecureConfig { psk: None }).is_none());
+    }
+
+    #[test]
+    fn keys_parse_from_hex() {
+        let hex = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+        assert_eq!(parse_key(hex), Some(std::array::from_fn(|i| i as u8)));
+        assert_eq!(parse_key(&hex[2..]), None);
+        assert_eq!(parse_key(&hex.replace('a', "g")), None);
+    }
+}
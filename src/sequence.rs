@@ -0,0 +1,84 @@
+/// A sequence counter that wraps around, as the reliable channel's u16 and input frames' u32 do.
+pub trait Sequence: Copy + Eq {
+    /// Whether `self` comes after `other`, taking whichever way round is the shorter distance.
+    fn newer_than(self, other: Self) -> bool;
+}
+
+impl Sequence for u16 {
+    fn newer_than(self, other: u16) -> bool {
+        self != other && self.wrapping_sub(other) < u16::MAX / 2
+    }
+}
+
+impl Sequence for u32 {
+    fn newer_than(self, other: u32) -> bool {
+        self != other && self.wrapping_sub(other) < u32::MAX / 2
+    }
+}
+
+/// Keeps the newest sequence seen, so older and repeated ones can be dropped.
+#[derive(Default)]
+pub struct Latest<T> {
+    latest: Option<T>,
+}
+
+impl<T: Sequence> Latest<T> {
+    /// Whether `sequence` is newer than anything accepted before, in which case it becomes the newest.
+    pub fn accept(&mut self, sequence: T) -> bool {
+        if self.latest.is_some_and(|latest| !sequence.newer_than(latest)) {
+            return false;
+        }
+        self.latest = Some(sequence);
+        true
+    }
+
+    /// Forgets the newest sequence, for a peer that starts counting over.
+    pub fn reset(&mut self) {
+        self.latest = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newer_wraps_around() {
+        assert!(2u16.newer_than(1));
+        assert!(!1u16.newer_than(2));
+        assert!(!7u16.newer_than(7));
+        assert!(0u16.newer_than(u16::MAX));
+        assert!(!u16::MAX.newer_than(0));
+        assert!(3u32.newer_than(u32::MAX - 3));
+        assert!(!(u32::MAX - 3).newer_than(3));
+    }
+
+    #[test]
+    fn duplicates_and_reordered_sequences_are_refused() {
+        let mut latest = Latest::default();
+        assert!(latest.accept(10u32));
+        assert!(!latest.accept(10));
+        assert!(latest.accept(12));
+        // 11 arrived after 12.
+        assert!(!latest.accept(11));
+        assert!(latest.accept(13));
+    }
+
+    #[test]
+    fn sequences_carry_on_across_the_wrap() {
+        let mut latest = Latest::default();
+        assert!(latest.accept(u32::MAX - 1));
+        assert!(latest.accept(u32::MAX));
+        assert!(latest.accept(0));
+        assert!(!latest.accept(u32::MAX));
+        assert!(latest.accept(1));
+    }
+
+    #[test]
+    fn reset_accepts_anything() {
+        let mut latest = Latest::default();
+        assert!(latest.accept(500u32));
+        latest.reset();
+        assert!(latest.accept(3));
+    }
+}
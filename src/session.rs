@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use crate::reliable::ReliableReceiver;
+use crate::secure::{self, SecureSession};
+use crate::sequence::Latest;
+
+// How often a session sends a PathChallenge, whatever addresses its packets turn up from.
+const PROBE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A PathChallenge sent to an address a secure session's packets came from.
+pub struct PathProbe {
+    pub addr: SocketAddr,
+    pub nonce: u64,
+    pub sent: Instant,
+}
+
+/// What the receive loop keeps for one client session.
+pub struct Session {
+    pub addr: SocketAddr,
+    pub last_heard: Instant,
+    // When the tick thread was last told the session is alive.
+    pub last_reported: Option<Instant>,
+    // The newest input frame queued, so repeated and reordered ones are dropped.
+    pub input_sequence: Latest<u32>,
+    pub reliable: ReliableReceiver,
+    pub secure: Option<SecureSession>,
+    // The newest PathChallenge, until the session moves.
+    pub probe: Option<PathProbe>,
+}
+
+impl Session {
+    pub fn new(addr: SocketAddr) -> Session {
+        Session {
+            addr,
+            last_heard: Instant::now(),
+            last_reported: None,
+            input_sequence: Latest::default(),
+            reliable: ReliableReceiver::new(),
+            secure: None,
+            probe: None,
+        }
+    }
+
+    /// Whether a PathChallenge may go out now. Rate limited per session, so replaying sealed
+    /// packets from many spoofed addresses can't be turned into a flood.
+    pub fn may_probe(&self, now: Instant) -> bool {
+        self.probe.as_ref().is_none_or(|probe| now.duration_since(probe.sent) >= PROBE_INTERVAL)
+    }
+
+    /// Whether a PathResponse with `nonce` from `addr` answers the newest PathChallenge.
+    pub fn answers_probe(&self, addr: &SocketAddr, nonce: u64) -> bool {
+        self.probe.as_ref().is_some_and(|probe| probe.addr == *addr && probe.nonce == nonce)
+    }
+
+    /// Sends a finished packet to wherever the client is now, sealed if the session is secure.
+    pub fn send(&self, socket: &UdpSocket, packet: &[u8]) -> io::Result<()> {
+        let sealer = self.secure.as_ref().map(|secure| secure.link.sealer.as_ref());
+        secure::send_to(socket, &self.addr, packet, sealer)
+    }
+
+    fn key_id(&self) -> Option<u64> {
+        self.secure.as_ref().map(|s| s.key_id())
+    }
+}
+
+/// Sessions by token, with indexes from source address and from secure key id.
+///
+/// A session only ever has one address, so a client that moves takes its session with it and the
+/// old address stops counting as verified.
+pub struct Sessions {
+    by_id: HashMap<u64, Session>,
+    by_addr: HashMap<SocketAddr, u64>,
+    by_key_id: HashMap<u64, u64>,
+}
+
+impl Sessions {
+    pub fn new() -> Sessions {
+        Sessions {
+            by_id: HashMap::new(),
+            by_addr: HashMap::new(),
+            by_key_id: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, session_id: u64) -> Option<&Session> {
+        self.by_id.get(&session_id)
+    }
+
+    pub fn get_mut(&mut self, session_id: u64) -> Option<&mut Session> {
+        self.by_id.get_mut(&session_id)
+    }
+
+    pub fn id_for_addr(&self, addr: &SocketAddr) -> Option<u64> {
+        self.by_addr.get(addr).copied()
+    }
+
+    /// The session a sealed datagram claims to belong to.
+    pub fn id_for_datagram(&self, datagram: &[u8]) -> Option<u64> {
+        self.by_key_id.get(&secure::key_id(datagram)?).copied()
+    }
+
+    pub fn insert(&mut self, session_id: u64, session: Session) {
+        self.remove(session_id);
+        if let Some(previous) = self.by_addr.insert(session.addr, session_id) {
+            self.remove(previous);
+            self.by_addr.insert(session.addr, session_id);
+        }
+        if let Some(key_id) = session.key_id() {
+            self.by_key_id.insert(key_id, session_id);
+        }
+        self.by_id.insert(session_id, session);
+    }
+
+    /// Replaces the session's keys after a new key exchange.
+    pub fn set_secure(&mut self, session_id: u64, secure: SecureSession) {
+        let Some(session) = self.by_id.get_mut(&session_id) else {
+            return;
+        };
+        if let Some(old) = session.key_id() {
+            self.by_key_id.remove(&old);
+        }
+        self.by_key_id.insert(secure.key_id(), session_id);
+        session.secure = Some(secure);
+    }
+
+    /// Moves a session to `addr`. Returns the address it moved from, if it moved at all.
+    pub fn migrate(&mut self, session_id: u64, addr: SocketAddr) -> Option<SocketAddr> {
+        let session = self.by_id.get(&session_id)?;
+        if session.addr == addr {
+            return None;
+        }
+        let old = session.addr;
+        // Whoever was at the new address before has been replaced by this client.
+        if let Some(displaced) = self.by_addr.get(&addr).copied() {
+            self.remove(displaced);
+        }
+        self.by_addr.remove(&old);
+        self.by_addr.insert(addr, session_id);
+        let session = self.by_id.get_mut(&session_id)?;
+        session.addr = addr;
+        session.probe = None;
+        Some(old)
+    }
+
+    pub fn remove(&mut self, session_id: u64) -> Option<Session> {
+        let session = self.by_id.remove(&session_id)?;
+        if self.by_addr.get(&session.addr) == Some(&session_id) {
+            self.by_addr.remove(&session.addr);
+        }
+        if let Some(key_id) = session.key_id() {
+            self.by_key_id.remove(&key_id);
+        }
+        Some(session)
+    }
+
+    /// Forgets sessions that have been silent for longer than `timeout`.
+    pub fn prune(&mut self, timeout: Duration) {
+        let stale: Vec<u64> = self
+            .by_id
+            .iter()
+            .filter(|(_, s)| s.last_heard.elapsed() >= timeout)
+            .map(|(id, _)| *id)
+            .collect();
+        for session_id in stale {
+            self.remove(session_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    #[test]
+    fn migration_moves_the_address_index() {
+        let mut sessions = Sessions::new();
+        sessions.insert(7, Session::new(addr(1)));
+        assert_eq!(sessions.migrate(7, addr(1)), None);
+        assert_eq!(sessions.migrate(7, addr(2)), Some(addr(1)));
+        assert_eq!(sessions.id_for_addr(&addr(1)), None);
+        assert_eq!(sessions.id_for_addr(&addr(2)), Some(7));
+        assert_eq!(sessions.get(7).unwrap().addr, addr(2));
+    }
+
+    #[test]
+    fn migrating_onto_a_used_address_displaces_its_session() {
+        let mut sessions = Sessions::new();
+        sessions.insert(1, Session::new(addr(1)));
+        sessions.insert(2, Session::new(addr(2)));
+        sessions.migrate(1, addr(2));
+        assert!(sessions.get(2).is_none());
+        assert_eq!(sessions.id_for_addr(&addr(2)), Some(1));
+    }
+
+    #[test]
+    fn new_session_on_an_address_replaces_the_old_one() {
+        let mut sessions = Sessions::new();
+        sessions.insert(1, Session::new(addr(1)));
+        sessions.insert(2, Session::new(addr(1)));
+        assert!(sessions.get(1).is_none());
+        assert_eq!(sessions.id_for_addr(&addr(1)), Some(2));
+    }
+
+    #[test]
+    fn path_probes_are_rate_limited_and_answered_from_their_address_only() {
+        let mut session = Session::new(addr(1));
+        let now = Instant::now();
+        assert!(session.may_probe(now));
+        assert!(!session.answers_probe(&addr(2), 0));
+        session.probe = Some(PathProbe { addr: addr(2), nonce: 42, sent: now });
+        assert!(!session.may_probe(now + PROBE_INTERVAL / 2));
+        assert!(session.may_probe(now + PROBE_INTERVAL));
+        assert!(session.answers_probe(&addr(2), 42));
+        assert!(!session.answers_probe(&addr(2), 43));
+        assert!(!session.answers_probe(&addr(3), 42));
+
+        let mut sessions = Sessions::new();
+        sessions.insert(1, session);
+        sessions.migrate(1, addr(2));
+        assert!(sessions.get(1).unwrap().probe.is_none());
+    }
+
+    #[test]
+    fn pruning_drops_silent_sessions() {
+        let mut sessions = Sessions::new();
+        sessions.insert(1, Session::new(addr(1)));
+        sessions.insert(2, Session::new(addr(2)));
+        sessions.get_mut(1).unwrap().last_heard -= Duration::from_secs(11);
+        sessions.prune(Duration::from_secs(10));
+        assert!(sessions.get(1).is_none());
+        assert_eq!(sessions.id_for_addr(&addr(1)), None);
+        assert!(sessions.get(2).is_some());
+    }
+}
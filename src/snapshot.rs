@@ -0,0 +1,282 @@
+use std::collections::VecDeque;
+
+use flatbuffers::{FlatBufferBuilder, UnionWIPOffset, WIPOffset};
+
+use crate::schema_generated::{
+    self, Color, PlayerArgs, PlayerDeltaBuilder, PlayersDeltaArgs, PlayersListArgs, ServerMessage,
+};
+
+// About half a second of snapshots at the default tick rate.
+const SNAPSHOT_HISTORY: usize = 32;
+
+const CHANGED_X: u8 = 1 << 0;
+const CHANGED_Y: u8 = 1 << 1;
+const CHANGED_COLOR: u8 = 1 << 2;
+const CHANGED_AIM: u8 = 1 << 3;
+const CHANGED_ALL: u8 = CHANGED_X | CHANGED_Y | CHANGED_COLOR | CHANGED_AIM;
+
+/// The replicated part of a player, as it appears in a snapshot.
+#[derive(Clone, Copy, PartialEq)]
+pub struct EntityState {
+    pub id: u32,
+    pub x: f32,
+    pub y: f32,
+    pub color: Color,
+    pub aim_x: f32,
+    pub aim_y: f32,
+}
+
+impl EntityState {
+    fn changes_since(&self, baseline: &EntityState) -> u8 {
+        let mut changed = 0;
+        if self.x != baseline.x {
+            changed |= CHANGED_X;
+        }
+        if self.y != baseline.y {
+            changed |= CHANGED_Y;
+        }
+        if self.color != baseline.color {
+            changed |= CHANGED_COLOR;
+        }
+        if self.aim_x != baseline.aim_x || self.aim_y != baseline.aim_y {
+            changed |= CHANGED_AIM;
+        }
+        changed
+    }
+}
+
+/// Per-recipient fields that go into every snapshot.
+pub struct SnapshotHeader {
+    pub tick: u64,
+    pub server_time: u64,
+    pub input_lead: u8,
+    pub last_processed_input: u32,
+    pub your_id: u32,
+}
+
+/// Snapshots recently sent to one client, used as delta baselines once the client acknowledges them.
+pub struct SnapshotHistory {
+    sent: VecDeque<(u64, Vec<EntityState>)>,
+    acked_tick: Option<u64>,
+}
+
+impl SnapshotHistory {
+    pub fn new() -> SnapshotHistory {
+        SnapshotHistory {
+            sent: VecDeque::with_capacity(SNAPSHOT_HISTORY),
+            acked_tick: None,
+        }
+    }
+
+    pub fn ack(&mut self, tick: u64) {
+        if self.acked_tick.is_none_or(|acked| tick > acked) {
+            self.acked_tick = Some(tick);
+        }
+    }
+
+    fn baseline(&self) -> Option<&(u64, Vec<EntityState>)> {
+        let acked = self.acked_tick?;
+        self.sent.iter().find(|(tick, _)| *tick == acked)
+    }
+
+    /// Encodes `entities` as a delta against the last acknowledged snapshot, or in full when the
+    /// client has no baseline we still remember, and records them as a future baseline.
+    pub fn write(&mut self,
+                 builder: &mut FlatBufferBuilder,
+                 header: &SnapshotHeader,
+                 entities: &[EntityState]) -> (ServerMessage, WIPOffset<UnionWIPOffset>) {
+        let message = match self.baseline() {
+            Some((baseline_tick, baseline)) => (
+                ServerMessage::PlayersDelta,
+                write_delta(builder, header, *baseline_tick, baseline, entities),
+            ),
+            None => (ServerMessage::PlayersList, write_full(builder, header, entities)),
+        };
+
+        if self.sent.len() == SNAPSHOT_HISTORY {
+            self.sent.pop_front();
+        }
+        self.sent.push_back((header.tick, entities.to_vec()));
+        message
+    }
+}
+
+fn write_full(builder: &mut FlatBufferBuilder,
+              header: &SnapshotHeader,
+              entities: &[EntityState]) -> WIPOffset<UnionWIPOffset> {
+    let players_offsets: Vec<_> = entities
+        .iter()
+        .map(|e| {
+            let args = PlayerArgs {
+                id: e.id,
+                x: e.x,
+                y: e.y,
+                color: e.color,
+                aim_x: e.aim_x,
+                aim_y: e.aim_y,
+            };
+            schema_generated::Player::create(builder, &args)
+        })
+        .collect();
+
+    let players_vec = builder.create_vector(&players_offsets);
+    schema_generated::PlayersList::create(
+        builder,
+        &PlayersListArgs {
+            players: Some(players_vec),
+            last_processed_input: header.last_processed_input,
+            tick: header.tick,
+            your_id: header.your_id,
+            server_time: header.server_time,
+            input_lead: header.input_lead,
+        },
+    ).as_union_value()
+}
+
+fn write_delta(builder: &mut FlatBufferBuilder,
+               header: &SnapshotHeader,
+               baseline_tick: u64,
+               baseline: &[EntityState],
+               entities: &[EntityState]) -> WIPOffset<UnionWIPOffset> {
+    let mut players_offsets = vec![];
+    for entity in entities {
+        let changed = match baseline.iter().find(|b| b.id == entity.id) {
+            Some(previous) => entity.changes_since(previous),
+            None => CHANGED_ALL,
+        };
+        if changed == 0 {
+            continue;
+        }
+
+        // Unchanged fields are left out of the table entirely, which is where the savings come from.
+        let mut player = PlayerDeltaBuilder::new(builder);
+        player.add_id(entity.id);
+        player.add_changed(changed);
+        if changed & CHANGED_X != 0 {
+            player.add_x(entity.x);
+        }
+        if changed & CHANGED_Y != 0 {
+            player.add_y(entity.y);
+        }
+        if changed & CHANGED_COLOR != 0 {
+            player.add_color(entity.color);
+        }
+        if changed & CHANGED_AIM != 0 {
+            player.add_aim_x(entity.aim_x);
+            player.add_aim_y(entity.aim_y);
+        }
+        players_offsets.push(player.finish());
+    }
+
+    let removed: Vec<u32> = baseline
+        .iter()
+        .filter(|b| !entities.iter().any(|e| e.id == b.id))
+        .map(|b| b.id)
+        .collect();
+
+    let players_vec = builder.create_vector(&players_offsets);
+    let removed_vec = builder.create_vector(&removed);
+    schema_generated::PlayersDelta::create(
+        builder,
+        &PlayersDeltaArgs {
+            baseline_tick,
+            tick: header.tick,
+            last_processed_input: header.last_processed_input,
+            your_id: header.your_id,
+            players: Some(players_vec),
+            removed: Some(removed_vec),
+            server_time: header.server_time,
+            input_lead: header.input_lead,
+        },
+    ).as_union_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::finish_server_packet;
+    use crate::schema_generated::ServerPacket;
+    use flatbuffers::root;
+
+    fn entity(id: u32, x: f32) -> EntityState {
+        EntityState { id, x, y: 10.0, color: Color::Red, aim_x: 1.0, aim_y: 0.0 }
+    }
+
+    fn write(history: &mut SnapshotHistory, tick: u64, entities: &[EntityState]) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::new();
+        let header = SnapshotHeader { tick, server_time: 0, input_lead: 0, last_processed_input: 0, your_id: 1 };
+        let (message_type, message) = history.write(&mut builder, &header, entities);
+        finish_server_packet(&mut builder, message_type, message);
+        builder.finished_data().to_vec()
+    }
+
+    fn is_full(packet: &[u8]) -> bool {
+        root::<ServerPacket>(packet).unwrap().message_type() == ServerMessage::PlayersList
+    }
+
+    #[test]
+    fn sends_full_snapshots_until_one_is_acknowledged() {
+        let mut history = SnapshotHistory::new();
+        let entities = [entity(1, 0.0), entity(2, 0.0)];
+        let first = write(&mut history, 1, &entities);
+        let packet = root::<ServerPacket>(&first).unwrap();
+        assert_eq!(packet.message_as_players_list().unwrap().players().unwrap().len(), 2);
+        assert!(is_full(&write(&mut history, 2, &entities)));
+
+        history.ack(2);
+        assert!(!is_full(&write(&mut history, 3, &entities)));
+    }
+
+    #[test]
+    fn deltas_carry_only_changed_fields_and_removals() {
+        let mut history = SnapshotHistory::new();
+        write(&mut history, 1, &[entity(1, 0.0), entity(2, 0.0), entity(3, 0.0), entity(4, 0.0)]);
+        history.ack(1);
+
+        let mut recolored = entity(3, 0.0);
+        recolored.color = Color::Blue;
+        recolored.aim_y = 1.0;
+        let bytes = write(&mut history, 2, &[entity(1, 5.0), entity(2, 0.0), recolored, entity(5, 7.0)]);
+
+        let packet = root::<ServerPacket>(&bytes).unwrap();
+        let delta = packet.message_as_players_delta().unwrap();
+        assert_eq!(delta.baseline_tick(), 1);
+        let changes: Vec<(u32, u8)> = delta.players().unwrap().iter().map(|p| (p.id(), p.changed())).collect();
+        // Player 2 didn't change, so it isn't in the delta at all.
+        assert_eq!(
+            changes,
+            vec![(1, CHANGED_X), (3, CHANGED_COLOR | CHANGED_AIM), (5, CHANGED_ALL)]
+        );
+        let players: Vec<_> = delta.players().unwrap().iter().collect();
+        assert_eq!(players[0].x(), 5.0);
+        assert_eq!(players[1].color(), Color::Blue);
+        assert_eq!((players[1].aim_x(), players[1].aim_y()), (1.0, 1.0));
+        assert_eq!(players[2].x(), 7.0);
+        assert_eq!(delta.removed().unwrap().iter().collect::<Vec<u32>>(), vec![4]);
+    }
+
+    #[test]
+    fn falls_back_to_full_when_the_baseline_is_gone() {
+        let mut history = SnapshotHistory::new();
+        let entities = [entity(1, 0.0)];
+        write(&mut history, 1, &entities);
+        history.ack(1);
+        for tick in 2..=SNAPSHOT_HISTORY as u64 + 1 {
+            assert!(!is_full(&write(&mut history, tick, &entities)));
+        }
+        // Tick 1 has dropped out of the history by now.
+        assert!(is_full(&write(&mut history, SNAPSHOT_HISTORY as u64 + 2, &entities)));
+    }
+
+    #[test]
+    fn falls_back_to_full_when_the_acked_tick_was_never_sent() {
+        let mut history = SnapshotHistory::new();
+        let entities = [entity(1, 0.0)];
+        write(&mut history, 1, &entities);
+        history.ack(7);
+        assert!(is_full(&write(&mut history, 2, &entities)));
+        // An older ack doesn't replace a newer one.
+        history.ack(1);
+        assert!(is_full(&write(&mut history, 3, &entities)));
+    }
+}
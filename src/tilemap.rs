@@ -0,0 +1,242 @@
+use crate::collision::Body;
+
+/// A grid of solid and empty square tiles. Everything outside the grid counts as solid, so the
+/// map's edges are walls, floor and ceiling.
+#[derive(Clone, Debug)]
+pub struct TileMap {
+    width: usize,
+    height: usize,
+    tile_size: f32,
+    solid: Vec<bool>,
+}
+
+impl TileMap {
+    /// An empty map `width` by `height` tiles.
+    pub fn new(width: usize, height: usize, tile_size: f32) -> TileMap {
+        TileMap {
+            width,
+            height,
+            tile_size,
+            solid: vec![false; width * height],
+        }
+    }
+
+    /// A map drawn as text, one string per row: `#` is solid, anything else is empty. Rows shorter
+    /// than the longest are padded with empty tiles.
+    pub fn from_rows(rows: &[&str], tile_size: f32) -> TileMap {
+        let width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+        let mut map = TileMap::new(width, rows.len(), tile_size);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, tile) in row.chars().enumerate() {
+                map.set_solid(x, y, tile == '#');
+            }
+        }
+        map
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn tile_size(&self) -> f32 {
+        self.tile_size
+    }
+
+    pub fn pixel_width(&self) -> f32 {
+        self.width as f32 * self.tile_size
+    }
+
+    pub fn pixel_height(&self) -> f32 {
+        self.height as f32 * self.tile_size
+    }
+
+    pub fn set_solid(&mut self, x: usize, y: usize, solid: bool) {
+        if x < self.width && y < self.height {
+            self.solid[y * self.width + x] = solid;
+        }
+    }
+
+    pub fn is_solid(&self, x: i64, y: i64) -> bool {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return true;
+        }
+        self.solid[y as usize * self.width + x as usize]
+    }
+
+    /// Tile coordinates of every solid tile the box overlaps, edges excluded.
+    fn solid_tiles_under(&self, x: f32, y: f32, size: f32) -> impl Iterator<Item = (i64, i64)> + '_ {
+        let first = |v: f32| (v / self.tile_size).floor() as i64;
+        // A box ending exactly on a tile edge doesn't reach into the next tile.
+        let last = |v: f32| (v / self.tile_size).ceil() as i64 - 1;
+        let (x0, x1, y0, y1) = (first(x), last(x + size), first(y), last(y + size));
+        (y0..=y1)
+            .flat_map(move |ty| (x0..=x1).map(move |tx| (tx, ty)))
+            .filter(|&(tx, ty)| self.is_solid(tx, ty))
+    }
+
+    /// Moves `body` by its velocity over `dt` seconds, stopping it at solid tiles. X and y are
+    /// moved one after the other so sliding along a floor or wall never snags on tile seams, and
+    /// long moves go in steps of half a tile so fast bodies can't pass through thin platforms.
+    /// Sets `grounded` when the body comes to rest on top of a tile.
+    pub fn move_body(&self, body: &mut Body, dt: f32) {
+        let (dx, dy) = (body.vel_x * dt, body.vel_y * dt);
+        let steps = ((dx.abs().max(dy.abs()) / (self.tile_size / 2.0)).ceil() as usize).max(1);
+        for _ in 0..steps {
+            if body.vel_x != 0.0 {
+                body.x += dx / steps as f32;
+                self.stop_x(body, dx);
+            }
+            if body.vel_y != 0.0 {
+                body.y += dy / steps as f32;
+                self.stop_y(body, dy);
+            }
+        }
+    }
+
+    fn stop_x(&self, body: &mut Body, dx: f32) {
+        for (tx, _) in self.solid_tiles_under(body.x, body.y, body.size).collect::<Vec<_>>() {
+            let left = tx as f32 * self.tile_size;
+            if dx > 0.0 {
+                body.x = body.x.min(left - body.size);
+            } else {
+                body.x = body.x.max(left + self.tile_size);
+            }
+            body.vel_x = 0.0;
+        }
+    }
+
+    fn stop_y(&self, body: &mut Body, dy: f32) {
+        for (_, ty) in self.solid_tiles_under(body.x, body.y, body.size).collect::<Vec<_>>() {
+            let top = ty as f32 * self.tile_size;
+            if dy > 0.0 {
+                body.y = body.y.min(top - body.size);
+                body.grounded = true;
+            } else {
+                body.y = body.y.max(top + self.tile_size);
+            }
+            body.vel_y = 0.0;
+        }
+    }
+
+    /// Pushes `body` out of any solid tile it ended up in, along the shallower axis. Meant for
+    /// bodies that were shoved by other bodies rather than moved by `move_body`. Sets `grounded`
+    /// when the body ends up standing on a tile.
+    pub fn push_out(&self, body: &mut Body) {
+        let tiles: Vec<(i64, i64)> = self.solid_tiles_under(body.x, body.y, body.size).collect();
+        for (tx, ty) in tiles {
+            let tile = Body {
+                x: tx as f32 * self.tile_size,
+                y: ty as f32 * self.tile_size,
+                vel_x: 0.0,
+                vel_y: 0.0,
+                size: self.tile_size,
+                grounded: true,
+            };
+            let (overlap_x, overlap_y) = body.penetration(&tile);
+            if overlap_x <= 0.0 || overlap_y <= 0.0 {
+                continue;
+            }
+            if overlap_y < overlap_x {
+                if body.y < tile.y {
+                    body.y -= overlap_y;
+                    body.vel_y = body.vel_y.min(0.0);
+                    body.grounded = true;
+                } else {
+                    body.y += overlap_y;
+                    body.vel_y = body.vel_y.max(0.0);
+                }
+            } else if body.x < tile.x {
+                body.x -= overlap_x;
+                body.vel_x = body.vel_x.min(0.0);
+            } else {
+                body.x += overlap_x;
+                body.vel_x = body.vel_x.max(0.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn level() -> TileMap {
+        TileMap::from_rows(
+            &[
+                "..........",
+                "..........",
+                "......###.",
+                "..........",
+                "..........",
+            ],
+            16.0,
+        )
+    }
+
+    fn body(x: f32, y: f32, vel_x: f32, vel_y: f32) -> Body {
+        Body { x, y, vel_x, vel_y, size: 12.0, grounded: false }
+    }
+
+    #[test]
+    fn lands_on_a_platform_and_on_the_floor() {
+        let map = level();
+        let mut on_platform = body(100.0, 10.0, 0.0, 1200.0);
+        map.move_body(&mut on_platform, DT);
+        assert_eq!(on_platform.y, 32.0 - 12.0);
+        assert!(on_platform.grounded);
+        assert_eq!(on_platform.vel_y, 0.0);
+
+        let mut on_floor = body(10.0, 60.0, 0.0, 600.0);
+        map.move_body(&mut on_floor, DT);
+        assert_eq!(on_floor.y, map.pixel_height() - 12.0);
+        assert!(on_floor.grounded);
+    }
+
+    #[test]
+    fn fast_bodies_do_not_tunnel_through_platforms() {
+        let map = level();
+        let mut falling = body(100.0, 0.0, 0.0, 60.0 * 70.0);
+        map.move_body(&mut falling, DT);
+        assert_eq!(falling.y, 32.0 - 12.0);
+    }
+
+    #[test]
+    fn slides_along_the_floor_and_stops_at_walls() {
+        let map = level();
+        let mut sliding = body(10.0, map.pixel_height() - 12.0, 600.0, 0.0);
+        map.move_body(&mut sliding, DT);
+        assert_eq!(sliding.x, 20.0);
+        assert_eq!(sliding.y, map.pixel_height() - 12.0);
+
+        let mut at_wall = body(map.pixel_width() - 14.0, 40.0, 600.0, 0.0);
+        map.move_body(&mut at_wall, DT);
+        assert_eq!(at_wall.x, map.pixel_width() - 12.0);
+        assert_eq!(at_wall.vel_x, 0.0);
+
+        // Bumping into the underside of the platform stops the rise without grounding.
+        let mut jumping = body(100.0, 50.0, 0.0, -1200.0);
+        map.move_body(&mut jumping, DT);
+        assert_eq!(jumping.y, 48.0);
+        assert!(!jumping.grounded);
+    }
+
+    #[test]
+    fn pushed_bodies_are_moved_out_of_tiles() {
+        let map = level();
+        let mut sunk = body(100.0, 22.0, 0.0, 30.0);
+        map.push_out(&mut sunk);
+        assert_eq!(sunk.y, 20.0);
+        assert!(sunk.grounded);
+
+        // Touching the underside isn't overlapping.
+        let mut below = body(90.0, 48.0, 0.0, 0.0);
+        map.push_out(&mut below);
+        assert_eq!((below.x, below.y), (90.0, 48.0));
+    }
+}
@@ -0,0 +1,128 @@
+use std::time::{Duration, Instant};
+
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Paces the simulation in fixed steps. Real time goes into an accumulator and comes out as whole
+/// steps, so a slow tick is made up for with extra steps instead of slowing the game down.
+pub struct FixedStep {
+    step: Duration,
+    // After this many steps in one go we stop catching up and drop the rest of the backlog.
+    max_steps: u32,
+    accumulator: Duration,
+    last: Instant,
+    overruns: u32,
+    worst_overrun: Duration,
+    caught_up: u64,
+    dropped: u64,
+    last_report: Instant,
+}
+
+impl FixedStep {
+    pub fn new(step: Duration, max_steps: u32) -> FixedStep {
+        let now = Instant::now();
+        FixedStep {
+            step,
+            max_steps,
+            accumulator: Duration::ZERO,
+            last: now,
+            overruns: 0,
+            worst_overrun: Duration::ZERO,
+            caught_up: 0,
+            dropped: 0,
+            last_report: now,
+        }
+    }
+
+    /// How many steps to simulate to catch up with `now`.
+    pub fn advance(&mut self, now: Instant) -> u32 {
+        self.accumulator += now.saturating_duration_since(self.last);
+        self.last = now;
+
+        let due = self.accumulator.as_nanos() / self.step.as_nanos();
+        let steps = due.min(self.max_steps as u128) as u32;
+        if due > steps as u128 {
+            self.dropped += (due - steps as u128) as u64;
+            self.accumulator = Duration::from_nanos((self.accumulator.as_nanos() % self.step.as_nanos()) as u64);
+        } else {
+            self.accumulator -= self.step * steps;
+        }
+        if steps > 1 {
+            self.caught_up += steps as u64 - 1;
+        }
+        steps
+    }
+
+    /// Time left until the next step is due.
+    pub fn until_next(&self) -> Duration {
+        self.step.saturating_sub(self.accumulator)
+    }
+
+    /// Notes how long one round of steps took, to report when it didn't fit in a step.
+    pub fn record_work(&mut self, took: Duration) {
+        if took > self.step {
+            self.overruns += 1;
+            self.worst_overrun = self.worst_overrun.max(took);
+        }
+    }
+
+    /// Logs overruns since the last report, at most once per `REPORT_INTERVAL`.
+    pub fn report(&mut self) {
+        if self.last_report.elapsed() < REPORT_INTERVAL {
+            return;
+        }
+        self.last_report = Instant::now();
+        if self.overruns == 0 && self.caught_up == 0 && self.dropped == 0 {
+            return;
+        }
+        println!(
+            "Tick overran {} times (worst {:?} for a {:?} step), {} steps caught up, {} dropped",
+            self.overruns, self.worst_overrun, self.step, self.caught_up, self.dropped
+        );
+        self.overruns = 0;
+        self.worst_overrun = Duration::ZERO;
+        self.caught_up = 0;
+        self.dropped = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: Duration = Duration::from_millis(10);
+
+    #[test]
+    fn steps_come_out_of_the_accumulator() {
+        let mut clock = FixedStep::new(STEP, 5);
+        let start = clock.last;
+        assert_eq!(clock.advance(start + Duration::from_millis(4)), 0);
+        assert_eq!(clock.until_next(), Duration::from_millis(6));
+        assert_eq!(clock.advance(start + Duration::from_millis(13)), 1);
+        assert_eq!(clock.until_next(), Duration::from_millis(7));
+        // A slow round is caught up with extra steps, remainder included.
+        assert_eq!(clock.advance(start + Duration::from_millis(38)), 2);
+        assert_eq!(clock.until_next(), Duration::from_millis(2));
+        assert_eq!(clock.caught_up, 1);
+        assert_eq!(clock.dropped, 0);
+    }
+
+    #[test]
+    fn a_long_stall_drops_the_backlog() {
+        let mut clock = FixedStep::new(STEP, 5);
+        let start = clock.last;
+        assert_eq!(clock.advance(start + Duration::from_millis(1_003)), 5);
+        assert_eq!(clock.dropped, 95);
+        assert_eq!(clock.until_next(), Duration::from_millis(7));
+        assert_eq!(clock.advance(start + Duration::from_millis(1_010)), 1);
+    }
+
+    #[test]
+    fn only_slow_rounds_count_as_overruns() {
+        let mut clock = FixedStep::new(STEP, 5);
+        clock.record_work(STEP);
+        clock.record_work(Duration::from_millis(25));
+        clock.record_work(Duration::from_millis(12));
+        assert_eq!(clock.overruns, 2);
+        assert_eq!(clock.worst_overrun, Duration::from_millis(25));
+    }
+}
@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use flatbuffers::{InvalidFlatbuffer, VerifierOptions};
+
+use crate::protocol::PROTOCOL_VERSION;
+use crate::schema_generated::{ClientMessage, ClientPacket};
+
+// Reassembled fragments are the largest thing we ever parse.
+const MAX_PACKET_SIZE: usize = 1 << 17;
+const MAX_DEPTH: usize = 8;
+const MAX_TABLES: usize = 64;
+const MAX_COMMANDS_PER_PACKET: usize = 16;
+const MAX_CHAT_BYTES: usize = 256;
+
+const REJECTIONS_BEFORE_BAN: u32 = 20;
+// Rejections older than this are forgiven when counting towards a ban.
+const REJECTION_WINDOW: Duration = Duration::from_secs(60);
+// Each ban lasts twice as long as the previous one for the same source.
+const FIRST_BAN: Duration = Duration::from_secs(5);
+const MAX_BAN: Duration = Duration::from_secs(600);
+const MAX_TRACKED_SOURCES: usize = 4096;
+
+/// Why an incoming packet was dropped.
+#[derive(Debug)]
+pub enum Rejection {
+    Malformed(InvalidFlatbuffer),
+    UnexpectedMessage(ClientMessage),
+    TooManyCommands(usize),
+    // The client's protocol version, and what it was trying to send.
+    VersionMismatch(u16, ClientMessage),
+    ChatTooLong(usize),
+    Unauthorized,
+    NonFiniteAxis,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejection::Malformed(e) => write!(f, "malformed packet: {}", e),
+            Rejection::UnexpectedMessage(message) => write!(f, "unexpected message {:?}", message),
+            Rejection::TooManyCommands(count) => write!(f, "{} commands in one packet", count),
+            Rejection::VersionMismatch(version, _) => write!(f, "protocol version {}", version),
+            Rejection::ChatTooLong(len) => write!(f, "{} bytes of chat in one message", len),
+            Rejection::Unauthorized => write!(f, "admin command with the wrong token"),
+            Rejection::NonFiniteAxis => write!(f, "NaN or infinite input axis"),
+        }
+    }
+}
+
+fn verifier_options() -> VerifierOptions {
+    VerifierOptions {
+        max_depth: MAX_DEPTH,
+        max_tables: MAX_TABLES,
+        max_apparent_size: MAX_PACKET_SIZE,
+        ignore_missing_null_terminator: false,
+    }
+}
+
+/// Verifies untrusted bytes as a ClientPacket and checks the limits the schema can't express.
+/// Never panics, whatever the input.
+pub fn parse_client_packet(bytes: &[u8]) -> Result<ClientPacket<'_>, Rejection> {
+    let packet = flatbuffers::root_with_opts::<ClientPacket>(&verifier_options(), bytes)
+        .map_err(Rejection::Malformed)?;
+
+    // Checked first: other versions may use message types we've never heard of.
+    if packet.protocol_version() != PROTOCOL_VERSION {
+        return Err(Rejection::VersionMismatch(packet.protocol_version(), packet.message_type()));
+    }
+    match packet.message_type() {
+        ClientMessage::PlayerCommands
+        | ClientMessage::PlayerInput
+        | ClientMessage::Connect
+        | ClientMessage::Disconnect
+        | ClientMessage::Fragment
+        | ClientMessage::Reliable
+        | ClientMessage::ReliableAck
+        | ClientMessage::Ping
+        | ClientMessage::Chat
+        | ClientMessage::Admin
+        | ClientMessage::PathResponse => {}
+        other => return Err(Rejection::UnexpectedMessage(other)),
+    }
+    if packet.message().is_none() {
+        return Err(Rejection::UnexpectedMessage(packet.message_type()));
+    }
+    if let Some(commands) = packet.message_as_player_commands().and_then(|c| c.commands()) {
+        if commands.len() > MAX_COMMANDS_PER_PACKET {
+            return Err(Rejection::TooManyCommands(commands.len()));
+        }
+    }
+    if let Some(input) = packet.message_as_player_input() {
+        if ![input.move_x(), input.aim_x(), input.aim_y()].iter().all(|axis| axis.is_finite()) {
+            return Err(Rejection::NonFiniteAxis);
+        }
+    }
+    if let Some(text) = packet.message_as_chat().and_then(|c| c.text()) {
+        if text.len() > MAX_CHAT_BYTES {
+            return Err(Rejection::ChatTooLong(text.len()));
+        }
+    }
+    Ok(packet)
+}
+
+struct Offender {
+    rejected: u64,
+    recent: u32,
+    last_rejected: Instant,
+    bans: u32,
+    banned_until: Option<Instant>,
+}
+
+/// Counts rejected packets per source host and bans hosts that keep sending them,
+/// for longer each time they come back and do it again.
+pub struct Offenders {
+    sources: HashMap<IpAddr, Offender>,
+}
+
+impl Offenders {
+    pub fn new() -> Offenders {
+        Offenders { sources: HashMap::new() }
+    }
+
+    pub fn is_banned(&self, ip: &IpAddr) -> bool {
+        self.sources
+            .get(ip)
+            .and_then(|o| o.banned_until)
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Records a rejected packet from `ip` and bans it if it has crossed the threshold.
+    pub fn record(&mut self, ip: IpAddr, rejection: &Rejection) {
+        if !self.sources.contains_key(&ip) && self.sources.len() >= MAX_TRACKED_SOURCES {
+            self.forget_stale();
+        }
+
+        let now = Instant::now();
+        let offender = self.sources.entry(ip).or_insert(Offender {
+            rejected: 0,
+            recent: 0,
+            last_rejected: now,
+            bans: 0,
+            banned_until: None,
+        });
+        if now.duration_since(offender.last_rejected) > REJECTION_WINDOW {
+            offender.recent = 0;
+        }
+        offender.rejected += 1;
+        offender.recent += 1;
+        offender.last_rejected = now;
+
+        if offender.recent >= REJECTIONS_BEFORE_BAN {
+            let ban = FIRST_BAN.saturating_mul(1 << offender.bans.min(16)).min(MAX_BAN);
+            offender.bans += 1;
+            offender.recent = 0;
+            offender.banned_until = Some(now + ban);
+            println!("Banned {} for {:?} after {} rejected packets (last: {})", ip, ban, offender.rejected, rejection);
+        }
+    }
+
+    fn forget_stale(&mut self) {
+        let now = Instant::now();
+        self.sources.retain(|_, o| {
+            o.banned_until.is_some_and(|until| now < until)
+                || now.duration_since(o.last_rejected) <= REJECTION_WINDOW
+        });
+        if self.sources.len() >= MAX_TRACKED_SOURCES {
+            // Everyone is still active; drop the quietest one so tracking stays bounded.
+            let quietest = self.sources.iter().min_by_key(|(_, o)| o.last_rejected).map(|(ip, _)| *ip);
+            if let Some(ip) = quietest {
+                self.sources.remove(&ip);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema_generated::{
+        Chat, ChatArgs, ClientPacketArgs, Disconnect, DisconnectArgs, Fragment, FragmentArgs, PlayerCommand,
+        PlayerCommands, PlayerCommandsArgs, PlayerInput, PlayerInputArgs, Reliable, ReliableArgs,
+    };
+    use flatbuffers::FlatBufferBuilder;
+
+    /// Small xorshift generator so the fuzz cases are reproducible without extra dependencies.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn bytes(&mut self, len: usize) -> Vec<u8> {
+            (0..len).map(|_| self.next() as u8).collect()
+        }
+    }
+
+    fn valid_packets() -> Vec<Vec<u8>> {
+        let mut packets = vec![];
+        let mut builder = FlatBufferBuilder::new();
+
+        let commands = builder.create_vector(&[PlayerCommand::Move_left, PlayerCommand::Jump]);
+        let message = PlayerCommands::create(
+            &mut builder,
+            &PlayerCommandsArgs { commands: Some(commands), sequence: 3, ack_tick: 9, tick: 12 },
+        );
+        let packet = ClientPacket::create(
+            &mut builder,
+            &ClientPacketArgs {
+                message_type: ClientMessage::PlayerCommands,
+                message: Some(message.as_union_value()),
+                session_id: 5,
+                protocol_version: PROTOCOL_VERSION,
+            },
+        );
+        builder.finish(packet, None);
+        packets.push(builder.finished_data().to_vec());
+
+        builder.reset();
+        let message = Disconnect::create(&mut builder, &DisconnectArgs {});
+        let packet = ClientPacket::create(
+            &mut builder,
+            &ClientPacketArgs {
+                message_type: ClientMessage::Disconnect,
+                message: Some(message.as_union_value()),
+                session_id: 5,
+                protocol_version: PROTOCOL_VERSION,
+            },
+        );
+        builder.finish(packet, None);
+        packets.push(builder.finished_data().to_vec());
+
+        let inner = packets[0].clone();
+        builder.reset();
+        let data = builder.create_vector(&inner);
+        let message = Fragment::create(
+            &mut builder,
+            &FragmentArgs { message_id: 1, index: 0, count: 2, data: Some(data) },
+        );
+        let packet = ClientPacket::create(
+            &mut builder,
+            &ClientPacketArgs {
+                message_type: ClientMessage::Fragment,
+                message: Some(message.as_union_value()),
+                session_id: 5,
+                protocol_version: PROTOCOL_VERSION,
+            },
+        );
+        builder.finish(packet, None);
+        packets.push(builder.finished_data().to_vec());
+
+        builder.reset();
+        let payload = builder.create_vector(&inner);
+        let message = Reliable::create(&mut builder, &ReliableArgs { sequence: 5, payload: Some(payload) });
+        let packet = ClientPacket::create(
+            &mut builder,
+            &ClientPacketArgs {
+                message_type: ClientMessage::Reliable,
+                message: Some(message.as_union_value()),
+                session_id: 5,
+                protocol_version: PROTOCOL_VERSION,
+            },
+        );
+        builder.finish(packet, None);
+        packets.push(builder.finished_data().to_vec());
+
+        packets.push(input(0.5, 0.0, -1.0));
+        packets.push(chat("gg"));
+        packets
+    }
+
+    fn input(move_x: f32, aim_x: f32, aim_y: f32) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::new();
+        let message = PlayerInput::create(
+            &mut builder,
+            &PlayerInputArgs { sequence: 4, ack_tick: 9, tick: 13, buttons: 0b101, move_x, aim_x, aim_y },
+        );
+        let packet = ClientPacket::create(
+            &mut builder,
+            &ClientPacketArgs {
+                message_type: ClientMessage::PlayerInput,
+                message: Some(message.as_union_value()),
+                session_id: 5,
+                protocol_version: PROTOCOL_VERSION,
+            },
+        );
+        builder.finish(packet, None);
+        builder.finished_data().to_vec()
+    }
+
+    fn chat(text: &str) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::new();
+        let text = builder.create_string(text);
+        let message = Chat::create(&mut builder, &ChatArgs { player_id: 0, text: Some(text) });
+        let packet = ClientPacket::create(
+            &mut builder,
+            &ClientPacketArgs {
+                message_type: ClientMessage::Chat,
+                message: Some(message.as_union_value()),
+                session_id: 5,
+                protocol_version: PROTOCOL_VERSION,
+            },
+        );
+        builder.finish(packet, None);
+        builder.finished_data().to_vec()
+    }
+
+    /// Reads every field of an accepted packet, which must not panic either.
+    fn touch(packet: &ClientPacket) {
+        if let Some(commands) = packet.message_as_player_commands() {
+            let _ = (commands.sequence(), commands.ack_tick(), commands.tick());
+            commands.commands().into_iter().flatten().for_each(|c| {
+                let _ = c.variant_name();
+            });
+        }
+        if let Some(input) = packet.message_as_player_input() {
+            let _ = (input.sequence(), input.ack_tick(), input.tick(), input.buttons());
+            let _ = (input.move_x(), input.aim_x(), input.aim_y());
+        }
+        let _ = packet.message_as_disconnect();
+        if let Some(fragment) = packet.message_as_fragment() {
+            let _ = (fragment.message_id(), fragment.index(), fragment.count());
+            let _ = fragment.data().map(|d| d.bytes().len());
+        }
+        if let Some(reliable) = packet.message_as_reliable() {
+            let _ = reliable.sequence();
+            if let Some(payload) = reliable.payload() {
+                if let Ok(inner) = parse_client_packet(payload.bytes()) {
+                    touch(&inner);
+                }
+            }
+        }
+        if let Some(ack) = packet.message_as_reliable_ack() {
+            let _ = ack.ack();
+        }
+        if let Some(chat) = packet.message_as_chat() {
+            let _ = (chat.player_id(), chat.text().map(str::len));
+        }
+        let _ = format!("{:?}", packet);
+    }
+
+    #[test]
+    fn valid_packets_are_accepted() {
+        for packet in valid_packets() {
+            touch(&parse_client_packet(&packet).unwrap());
+        }
+    }
+
+    #[test]
+    fn random_bytes_never_panic() {
+        let mut rng = Rng(0x9e3779b97f4a7c15);
+        for _ in 0..20_000 {
+            let len = rng.below(256);
+            let bytes = rng.bytes(len);
+            if let Ok(packet) = parse_client_packet(&bytes) {
+                touch(&packet);
+            }
+        }
+    }
+
+    #[test]
+    fn mutated_packets_never_panic() {
+        let mut rng = Rng(0xdeadbeefcafef00d);
+        let seeds = valid_packets();
+        for _ in 0..20_000 {
+            let mut bytes = seeds[rng.below(seeds.len())].clone();
+            match rng.below(4) {
+                0 => {
+                    let i = rng.below(bytes.len());
+                    bytes[i] ^= 1 << rng.below(8);
+                }
+                1 => bytes.truncate(rng.below(bytes.len())),
+                2 => {
+                    let i = rng.below(bytes.len());
+                    bytes[i] = rng.next() as u8;
+                }
+                _ => {
+                    let len = rng.below(32);
+                    bytes.extend(rng.bytes(len));
+                }
+            }
+            if let Ok(packet) = parse_client_packet(&bytes) {
+                touch(&packet);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_and_tiny_inputs_are_rejected() {
+        for len in 0..8 {
+            assert!(parse_client_packet(&vec![0u8; len]).is_err());
+        }
+    }
+
+    #[test]
+    fn command_flood_is_rejected() {
+        let mut builder = FlatBufferBuilder::new();
+        let commands = builder.create_vector(&[PlayerCommand::Move_right; MAX_COMMANDS_PER_PACKET + 1]);
+        let message = PlayerCommands::create(
+            &mut builder,
+            &PlayerCommandsArgs { commands: Some(commands), ..Default::default() },
+        );
+        let packet = ClientPacket::create(
+            &mut builder,
+            &ClientPacketArgs {
+                message_type: ClientMessage::PlayerCommands,
+                message: Some(message.as_union_value()),
+                session_id: 5,
+                protocol_version: PROTOCOL_VERSION,
+            },
+        );
+        builder.finish(packet, None);
+        assert!(matches!(
+            parse_client_packet(builder.finished_data()),
+            Err(Rejection::TooManyCommands(_))
+        ));
+    }
+
+    #[test]
+    fn oversized_chat_is_rejected() {
+        assert!(parse_client_packet(&chat(&"a".repeat(MAX_CHAT_BYTES))).is_ok());
+        assert!(matches!(
+            parse_client_packet(&chat(&"a".repeat(MAX_CHAT_BYTES + 1))),
+            Err(Rejection::ChatTooLong(_))
+        ));
+    }
+
+    #[test]
+    fn non_finite_axes_are_rejected() {
+        for (move_x, aim_x, aim_y) in [(f32::NAN, 0.0, 0.0), (0.0, f32::INFINITY, 0.0), (0.0, 0.0, f32::NEG_INFINITY)] {
+            assert!(matches!(parse_client_packet(&input(move_x, aim_x, aim_y)), Err(Rejection::NonFiniteAxis)));
+        }
+        // Out of range is fine; the server clamps it.
+        assert!(parse_client_packet(&input(7.0, 100.0, 0.0)).is_ok());
+    }
+
+    #[test]
+    fn other_protocol_versions_are_rejected_before_anything_else() {
+        // A newer client may send message types this server has never heard of.
+        let mut builder = FlatBufferBuilder::new();
+        let message = Disconnect::create(&mut builder, &DisconnectArgs {});
+        let packet = ClientPacket::create(
+            &mut builder,
+            &ClientPacketArgs {
+                message_type: ClientMessage(200),
+                message: Some(message.as_union_value()),
+                session_id: 5,
+                protocol_version: PROTOCOL_VERSION + 1,
+            },
+        );
+        builder.finish(packet, None);
+        assert!(matches!(
+            parse_client_packet(builder.finished_data()),
+            Err(Rejection::VersionMismatch(version, ClientMessage(200))) if version == PROTOCOL_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn repeat_offenders_get_banned_for_longer() {
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+        let rejection = Rejection::UnexpectedMessage(ClientMessage::NONE);
+        let mut offenders = Offenders::new();
+
+        for _ in 0..REJECTIONS_BEFORE_BAN - 1 {
+            offenders.record(ip, &rejection);
+        }
+        assert!(!offenders.is_banned(&ip));
+        offenders.record(ip, &rejection);
+        assert!(offenders.is_banned(&ip));
+        assert!(!offenders.is_banned(&other));
+        let first_ban = offenders.sources[&ip].banned_until.unwrap();
+
+        for _ in 0..REJECTIONS_BEFORE_BAN {
+            offenders.record(ip, &rejection);
+        }
+        let second_ban = offenders.sources[&ip].banned_until.unwrap();
+        assert!(second_ban - first_ban >= FIRST_BAN);
+        assert_eq!(offenders.sources[&ip].rejected, 2 * REJECTIONS_BEFORE_BAN as u64);
+    }
+}